@@ -0,0 +1,151 @@
+//!
+//! Blocking IPP client
+//!
+use std::io;
+
+use tokio::runtime::current_thread::Runtime;
+
+use ipp_proto::{ipp::DelimiterTag, operation::IppOperation, IppAttributes, JobInfo};
+
+use crate::{IppClient, IppError};
+
+/// Blocking wrapper around `IppClient` for synchronous callers.
+/// Requests are executed on a private single-threaded runtime.
+pub struct BlockingIppClient {
+    client: IppClient,
+    runtime: Runtime,
+}
+
+impl BlockingIppClient {
+    /// Create blocking client from the asynchronous one
+    pub fn new(client: IppClient) -> io::Result<BlockingIppClient> {
+        Ok(BlockingIppClient {
+            client,
+            runtime: Runtime::new()?,
+        })
+    }
+
+    /// Return the underlying asynchronous client
+    pub fn client(&self) -> &IppClient {
+        &self.client
+    }
+
+    /// Send IPP operation and wait for the response attributes
+    pub fn send<T>(&mut self, operation: T) -> Result<IppAttributes, IppError>
+    where
+        T: IppOperation,
+    {
+        self.runtime.block_on(self.client.send(operation))
+    }
+
+    /// Send a job listing operation, for example Get-Jobs, and iterate over the returned jobs.
+    /// If the request fails the iterator yields a single error. A job group without job-id
+    /// yields `IppError::MissingAttribute` and the iteration continues with the next job.
+    pub fn jobs<T>(&mut self, operation: T) -> impl Iterator<Item = Result<JobInfo, IppError>>
+    where
+        T: IppOperation,
+    {
+        let jobs = match self.runtime.block_on(self.client.send(operation)) {
+            Ok(attrs) => attrs
+                .groups_of(DelimiterTag::JobAttributes)
+                .into_iter()
+                .map(|group| {
+                    let job = JobInfo::from_group(group);
+                    match job.id() {
+                        Some(_) => Ok(job),
+                        None => Err(IppError::MissingAttribute),
+                    }
+                })
+                .collect(),
+            Err(e) => vec![Err(e)],
+        };
+        jobs.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ipp_proto::{
+        attribute::{PRINTER_NAME, PRINTER_STATE},
+        ipp::{IppVersion, Operation, PrinterState, StatusCode},
+        request::IppRequestResponse,
+        IppAttribute, IppOperationBuilder, IppValue,
+    };
+
+    use crate::{client::tests::mock_http_server, IppClientBuilder};
+
+    use super::*;
+
+    #[test]
+    fn test_blocking_send() {
+        let mut response = IppRequestResponse::new_response(IppVersion::Ipp11, StatusCode::SuccessfulOK, 1);
+        response.attributes_mut().add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_STATE, IppValue::Enum(PrinterState::Idle as i32)),
+        );
+        let mut body = Vec::new();
+        response.write(&mut body).unwrap();
+        let (uri, handle) = mock_http_server("application/ipp", body);
+
+        let mut client = IppClientBuilder::new(&uri).build_blocking().unwrap();
+        let attrs = client
+            .send(
+                IppOperationBuilder::get_printer_attributes()
+                    .attributes(&[PRINTER_STATE, PRINTER_NAME])
+                    .build(),
+            )
+            .unwrap();
+        let request = handle.join().unwrap();
+
+        assert!(request.starts_with("POST /ipp/print"));
+        assert_eq!(attrs.operation(), Some(Operation::GetPrinterAttributes));
+        assert_eq!(
+            attrs.groups_of(DelimiterTag::PrinterAttributes)[0].attributes()[PRINTER_STATE].value(),
+            &IppValue::Enum(PrinterState::Idle as i32)
+        );
+    }
+
+    #[test]
+    fn test_blocking_jobs() {
+        let mut body = vec![1, 1, 0, 0, 0, 0, 0, 1];
+        for id in &[Some(1), None, Some(3)] {
+            body.push(2);
+            if let Some(id) = id {
+                body.extend_from_slice(&[0x21, 0, 6]);
+                body.extend_from_slice(b"job-id");
+                body.extend_from_slice(&[0, 4, 0, 0, 0, *id]);
+            }
+            body.extend_from_slice(&[0x42, 0, 8]);
+            body.extend_from_slice(b"job-name");
+            body.extend_from_slice(&[0, 3]);
+            body.extend_from_slice(b"doc");
+        }
+        body.push(3);
+        let (uri, handle) = mock_http_server("application/ipp", body);
+
+        let mut client = BlockingIppClient::new(IppClientBuilder::new(&uri).build()).unwrap();
+        let jobs = client
+            .jobs(IppOperationBuilder::get_printer_attributes().build())
+            .collect::<Vec<_>>();
+        handle.join().unwrap();
+
+        assert_eq!(jobs.len(), 3);
+        assert_eq!(jobs[0].as_ref().ok().and_then(|j| j.id()), Some(1));
+        assert!(matches!(jobs[1], Err(IppError::MissingAttribute)));
+        assert_eq!(jobs[2].as_ref().ok().and_then(|j| j.id()), Some(3));
+    }
+
+    #[test]
+    fn test_blocking_jobs_error() {
+        let (uri, handle) = mock_http_server("text/html", b"<html></html>".to_vec());
+
+        let mut client = BlockingIppClient::new(IppClientBuilder::new(&uri).build()).unwrap();
+        let jobs = client
+            .jobs(IppOperationBuilder::get_printer_attributes().build())
+            .collect::<Vec<_>>();
+        handle.join().unwrap();
+
+        assert_eq!(jobs.len(), 1);
+        assert!(jobs[0].is_err());
+    }
+}
@@ -0,0 +1,264 @@
+//!
+//! TLS connection details, enabled with `tls-info` feature
+//!
+use std::{
+    error::Error,
+    fs, io,
+    net::{TcpStream, ToSocketAddrs},
+    path::PathBuf,
+    thread,
+    time::Duration,
+};
+
+use futures::{future, sync::oneshot, Future};
+use log::debug;
+use openssl::{
+    hash::MessageDigest,
+    pkcs12::Pkcs12,
+    ssl::{SslConnector, SslMethod, SslVerifyMode},
+    x509::X509,
+};
+
+use crate::{client::parse_uri, IppClient, IppError};
+
+/// TLS parameters negotiated with the printer
+#[derive(Clone, Debug, PartialEq)]
+pub struct TlsInfo {
+    /// Protocol version, for example `TLSv1.3`
+    pub protocol_version: String,
+    /// Cipher suite name in OpenSSL notation, for example `TLS_AES_256_GCM_SHA384`
+    pub cipher_suite: String,
+    /// SHA-256 fingerprint of the printer certificate as colon-separated uppercase hex
+    pub peer_cert_fingerprint: Option<String>,
+}
+
+fn tls_error<E: Into<Box<dyn Error + Send + Sync>>>(error: E) -> IppError {
+    IppError::Tls(error.into())
+}
+
+struct TlsProbe {
+    host: String,
+    port: u16,
+    ca_certs: Vec<PathBuf>,
+    client_identity: Option<(Vec<u8>, String)>,
+    verify_hostname: bool,
+    verify_certificate: bool,
+    timeout: u64,
+}
+
+impl TlsProbe {
+    // Connect to the first reachable address of the printer, applying the client timeout to each attempt
+    fn connect_tcp(&self) -> io::Result<TcpStream> {
+        let mut last_error = io::Error::new(io::ErrorKind::NotFound, format!("{}: no addresses", self.host));
+        for addr in (self.host.as_str(), self.port).to_socket_addrs()? {
+            let result = if self.timeout > 0 {
+                TcpStream::connect_timeout(&addr, Duration::from_secs(self.timeout))
+            } else {
+                TcpStream::connect(addr)
+            };
+            match result {
+                Ok(stream) => return Ok(stream),
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+
+    fn connect(self) -> Result<TlsInfo, IppError> {
+        let mut builder = SslConnector::builder(SslMethod::tls()).map_err(tls_error)?;
+        for cert_file in &self.ca_certs {
+            let buf = fs::read(cert_file)?;
+            let cert = X509::from_der(&buf)
+                .or_else(|_| X509::from_pem(&buf))
+                .map_err(tls_error)?;
+            builder.cert_store_mut().add_cert(cert).map_err(tls_error)?;
+        }
+        if let Some((ref der, ref password)) = self.client_identity {
+            let identity = Pkcs12::from_der(der)
+                .and_then(|p12| p12.parse2(password))
+                .map_err(tls_error)?;
+            if let (Some(cert), Some(key)) = (identity.cert, identity.pkey) {
+                builder.set_certificate(&cert).map_err(tls_error)?;
+                builder.set_private_key(&key).map_err(tls_error)?;
+            }
+        }
+        if !self.verify_certificate {
+            builder.set_verify(SslVerifyMode::NONE);
+        }
+        let config = builder
+            .build()
+            .configure()
+            .map_err(tls_error)?
+            .verify_hostname(self.verify_hostname);
+
+        let stream = self.connect_tcp()?;
+        if self.timeout > 0 {
+            stream.set_read_timeout(Some(Duration::from_secs(self.timeout)))?;
+            stream.set_write_timeout(Some(Duration::from_secs(self.timeout)))?;
+        }
+        let stream = config.connect(&self.host, stream).map_err(tls_error)?;
+        let ssl = stream.ssl();
+
+        let fingerprint = ssl
+            .peer_certificate()
+            .and_then(|cert| cert.digest(MessageDigest::sha256()).ok())
+            .map(|digest| {
+                digest
+                    .iter()
+                    .map(|b| format!("{:02X}", b))
+                    .collect::<Vec<_>>()
+                    .join(":")
+            });
+
+        Ok(TlsInfo {
+            protocol_version: ssl.version_str().to_owned(),
+            cipher_suite: ssl.current_cipher().map(|c| c.name().to_owned()).unwrap_or_default(),
+            peer_cert_fingerprint: fingerprint,
+        })
+    }
+}
+
+impl IppClient {
+    /// Connect to the printer and return the negotiated TLS parameters, or None for plaintext
+    /// ipp and http URIs.
+    ///
+    /// The HTTP client does not expose the TLS session of its connections, so the handshake is made
+    /// on a separate OpenSSL connection with the same certificates, verification settings and timeout
+    /// as the client. The result reflects what the printer negotiates with this client configuration.
+    /// It may differ from the connection used by a request, for example if the printer configuration
+    /// changes in between or the platform TLS library of the HTTP client prefers another cipher suite.
+    pub fn tls_info(&self) -> impl Future<Item = Option<TlsInfo>, Error = IppError> {
+        let ca_certs = self.ca_certs.clone();
+        let client_identity = self.client_identity.clone();
+        let verify_hostname = self.verify_hostname;
+        let verify_certificate = self.verify_certificate;
+        let timeout = self.timeout;
+
+        parse_uri(self.uri.clone()).and_then(move |url| {
+            if url.scheme() != "https" {
+                return future::Either::A(future::ok(None));
+            }
+            let probe = TlsProbe {
+                host: url
+                    .host_str()
+                    .unwrap_or_default()
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .to_owned(),
+                port: url.port_or_known_default().unwrap_or(443),
+                ca_certs,
+                client_identity,
+                verify_hostname,
+                verify_certificate,
+                timeout,
+            };
+            debug!("Probing TLS parameters of {}:{}", probe.host, probe.port);
+
+            let (tx, rx) = oneshot::channel();
+            thread::spawn(move || {
+                let _ = tx.send(probe.connect());
+            });
+            future::Either::B(rx.map_err(tls_error).and_then(|result| result.map(Some)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+    };
+
+    use openssl::{
+        asn1::Asn1Time,
+        bn::BigNum,
+        pkey::PKey,
+        rsa::Rsa,
+        ssl::SslAcceptor,
+        x509::{X509Builder, X509NameBuilder},
+    };
+
+    use crate::IppClientBuilder;
+
+    use super::*;
+
+    fn self_signed_cert() -> (X509, PKey<openssl::pkey::Private>) {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "localhost").unwrap();
+        let name = name.build();
+
+        let mut builder = X509Builder::new().unwrap();
+        builder.set_version(2).unwrap();
+        builder
+            .set_serial_number(&BigNum::from_u32(1).unwrap().to_asn1_integer().unwrap())
+            .unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder.set_not_before(&Asn1Time::days_from_now(0).unwrap()).unwrap();
+        builder.set_not_after(&Asn1Time::days_from_now(1).unwrap()).unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        (builder.build(), key)
+    }
+
+    #[test]
+    fn test_tls_info() {
+        let (cert, key) = self_signed_cert();
+        let expected = cert
+            .digest(MessageDigest::sha256())
+            .unwrap()
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect::<Vec<_>>()
+            .join(":");
+
+        let mut acceptor = SslAcceptor::mozilla_intermediate(SslMethod::tls()).unwrap();
+        acceptor.set_private_key(&key).unwrap();
+        acceptor.set_certificate(&cert).unwrap();
+        let acceptor = acceptor.build();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("ipps://{}/ipp/print", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut stream = acceptor.accept(stream).unwrap();
+            let _ = stream.read(&mut [0u8; 16]);
+        });
+
+        let client = IppClientBuilder::new(&uri).verify_certificate(false).build();
+        let info = client.tls_info().wait().unwrap().unwrap();
+        handle.join().unwrap();
+
+        assert!(info.protocol_version.starts_with("TLS"));
+        assert!(!info.cipher_suite.is_empty());
+        assert_eq!(info.peer_cert_fingerprint, Some(expected));
+    }
+
+    #[test]
+    fn test_tls_info_handshake_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("ipps://{}/ipp/print", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.read(&mut [0u8; 16]);
+            let _ = stream.write_all(b"HTTP/1.1 400 Bad Request\r\n\r\n");
+        });
+
+        let client = IppClientBuilder::new(&uri).verify_certificate(false).build();
+        let result = client.tls_info().wait();
+        handle.join().unwrap();
+
+        match result {
+            Err(IppError::Tls(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_tls_info_plaintext() {
+        let client = IppClientBuilder::new("ipp://localhost/ipp/print").build();
+        assert_eq!(client.tls_info().wait().unwrap(), None);
+    }
+}
@@ -3,12 +3,18 @@
 //!
 use std::{
     collections::HashMap,
-    io::{self, Write},
+    convert::TryFrom,
+    io::{self, Read, Write},
+    time::{Duration, SystemTime},
 };
 
 use byteorder::{BigEndian, WriteBytesExt};
 
-use crate::{ipp::*, IppValue, IppWriter};
+use crate::{
+    ipp::*,
+    parser::{IppParser, ParseError},
+    IppValue, IppWriter,
+};
 
 pub const ATTRIBUTES_CHARSET: &str = "attributes-charset";
 pub const ATTRIBUTES_NATURAL_LANGUAGE: &str = "attributes-natural-language";
@@ -28,6 +34,9 @@ pub const PRINTER_NAME: &str = "printer-name";
 pub const PRINTER_STATE: &str = "printer-state";
 pub const PRINTER_STATE_MESSAGE: &str = "printer-state-message";
 pub const PRINTER_STATE_REASONS: &str = "printer-state-reasons";
+pub const PRINTER_STATE_CHANGE_TIME: &str = "printer-state-change-time";
+pub const PRINTER_IMPRESSIONS_COMPLETED: &str = "printer-impressions-completed";
+pub const PRINTER_STATE_CHANGE_DATE_TIME: &str = "printer-state-change-date-time";
 pub const PRINTER_UP_TIME: &str = "printer-up-time";
 pub const PRINTER_URI: &str = "printer-uri";
 pub const PRINTER_URI_SUPPORTED: &str = "printer-uri-supported";
@@ -35,9 +44,21 @@ pub const QUEUED_JOB_COUNT: &str = "queued-job-count";
 pub const URI_AUTHENTICATION_SUPPORTED: &str = "uri-authentication-supported";
 pub const URI_SECURITY_SUPPORTED: &str = "uri-security-supported";
 pub const JOB_ID: &str = "job-id";
+pub const NUMBER_OF_DOCUMENTS: &str = "number-of-documents";
+pub const JOB_UUID: &str = "job-uuid";
+pub const JOB_K_OCTETS_PROCESSED: &str = "job-k-octets-processed";
+pub const JOB_IMPRESSIONS_COMPLETED: &str = "job-impressions-completed";
 pub const JOB_NAME: &str = "job-name";
+pub const JOB_MANDATORY_ATTRIBUTES: &str = "job-mandatory-attributes";
+pub const IPP_ATTRIBUTE_FIDELITY: &str = "ipp-attribute-fidelity";
 pub const JOB_STATE: &str = "job-state";
 pub const JOB_STATE_REASONS: &str = "job-state-reasons";
+pub const TIME_AT_CREATION: &str = "time-at-creation";
+pub const TIME_AT_PROCESSING: &str = "time-at-processing";
+pub const TIME_AT_COMPLETED: &str = "time-at-completed";
+pub const DATE_TIME_AT_CREATION: &str = "date-time-at-creation";
+pub const DATE_TIME_AT_PROCESSING: &str = "date-time-at-processing";
+pub const DATE_TIME_AT_COMPLETED: &str = "date-time-at-completed";
 pub const JOB_URI: &str = "job-uri";
 pub const LAST_DOCUMENT: &str = "last-document";
 pub const REQUESTING_USER_NAME: &str = "requesting-user-name";
@@ -47,8 +68,14 @@ pub const SIDES_SUPPORTED: &str = "sides-supported";
 pub const OUTPUT_MODE_SUPPORTED: &str = "output-mode-supported";
 pub const COLOR_SUPPORTED: &str = "color-supported";
 pub const PRINTER_INFO: &str = "printer-info";
+pub const PRINTER_KIND: &str = "printer-kind";
 pub const PRINTER_LOCATION: &str = "printer-location";
 pub const PRINTER_MORE_INFO: &str = "printer-more-info";
+pub const PRINTER_SUPPLY_INFO_URI: &str = "printer-supply-info-uri";
+pub const PRINTER_DEVICE_ID: &str = "printer-device-id";
+pub const PRINTER_FIRMWARE_NAME: &str = "printer-firmware-name";
+pub const PRINTER_FIRMWARE_STRING_VERSION: &str = "printer-firmware-string-version";
+pub const PRINTER_RESOLUTION: &str = "printer-resolution";
 pub const PRINTER_RESOLUTION_DEFAULT: &str = "printer-resolution-default";
 pub const PRINTER_RESOLUTION_SUPPORTED: &str = "printer-resolution-supported";
 pub const COPIES_SUPPORTED: &str = "copies-supported";
@@ -58,6 +85,16 @@ pub const PRINT_QUALITY_DEFAULT: &str = "print-quality-default";
 pub const PRINT_QUALITY_SUPPORTED: &str = "print-quality-supported";
 pub const FINISHINGS_DEFAULT: &str = "finishings-default";
 pub const FINISHINGS_SUPPORTED: &str = "finishings-supported";
+pub const FINISHINGS_COL: &str = "finishings-col";
+pub const FINISHING_TEMPLATE: &str = "finishing-template";
+pub const STITCHING: &str = "stitching";
+pub const STITCHING_LOCATIONS: &str = "stitching-locations";
+pub const STITCHING_OFFSET: &str = "stitching-offset";
+pub const STITCHING_REFERENCE_EDGE: &str = "stitching-reference-edge";
+pub const PUNCHING: &str = "punching";
+pub const PUNCHING_LOCATIONS: &str = "punching-locations";
+pub const PUNCHING_OFFSET: &str = "punching-offset";
+pub const PUNCHING_REFERENCE_EDGE: &str = "punching-reference-edge";
 pub const OUTPUT_BIN_DEFAULT: &str = "output-bin-default";
 pub const OUTPUT_BIN_SUPPORTED: &str = "output-bin-supported";
 pub const ORIENTATION_REQUESTED_DEFAULT: &str = "orientation-requested-default";
@@ -65,8 +102,66 @@ pub const ORIENTATION_REQUESTED_SUPPORTED: &str = "orientation-requested-support
 pub const MEDIA_DEFAULT: &str = "media-default";
 pub const MEDIA_SUPPORTED: &str = "media-supported";
 pub const PAGES_PER_MINUTE: &str = "pages-per-minute";
+pub const PAGES_PER_MINUTE_COLOR: &str = "pages-per-minute-color";
 pub const COLOR_MODE_SUPPORTED: &str = "color-mode-supported";
 pub const PRINT_COLOR_MODE_SUPPORTED: &str = "print-color-mode-supported";
+pub const DEVICE_URI: &str = "device-uri";
+pub const DOCUMENT_FORMAT: &str = "document-format";
+pub const DOCUMENT_URI: &str = "document-uri";
+pub const DOCUMENT_CHARSET: &str = "document-charset";
+pub const DOCUMENT_FORMAT_DETAILS: &str = "document-format-details";
+pub const DOCUMENT_FORMAT_VERSION: &str = "document-format-version";
+pub const DOCUMENT_NATURAL_LANGUAGE: &str = "document-natural-language";
+pub const DOCUMENT_SOURCE_APPLICATION_NAME: &str = "document-source-application-name";
+pub const DOCUMENT_SOURCE_APPLICATION_VERSION: &str = "document-source-application-version";
+pub const DOCUMENT_SOURCE_OS_NAME: &str = "document-source-os-name";
+pub const DOCUMENT_SOURCE_OS_VERSION: &str = "document-source-os-version";
+pub const FIRST_PRINTER_NAME: &str = "first-printer-name";
+pub const LIMIT: &str = "limit";
+pub const WHICH_JOBS: &str = "which-jobs";
+pub const MY_JOBS: &str = "my-jobs";
+pub const SIDES: &str = "sides";
+pub const MEDIA: &str = "media";
+pub const PAGE_RANGES: &str = "page-ranges";
+pub const PRINTER_UUID: &str = "printer-uuid";
+pub const OUTPUT_DEVICE_UUID: &str = "output-device-uuid";
+pub const MEDIA_COL: &str = "media-col";
+pub const JOB_SHEETS: &str = "job-sheets";
+pub const PRESENTATION_DIRECTION_NUMBER_UP: &str = "presentation-direction-number-up";
+pub const IMPOSITION_TEMPLATE: &str = "imposition-template";
+pub const JOB_SHEETS_COL: &str = "job-sheets-col";
+pub const JOB_ERROR_SHEET: &str = "job-error-sheet";
+pub const JOB_ERROR_SHEET_TYPE: &str = "job-error-sheet-type";
+pub const JOB_ERROR_SHEET_WHEN: &str = "job-error-sheet-when";
+pub const SEPARATOR_SHEETS: &str = "separator-sheets";
+pub const SEPARATOR_SHEETS_TYPE: &str = "separator-sheets-type";
+pub const JOB_CANCEL_AFTER: &str = "job-cancel-after";
+pub const JOB_HOLD_UNTIL: &str = "job-hold-until";
+pub const JOB_RETAIN_UNTIL: &str = "job-retain-until";
+pub const JOB_DELAY_OUTPUT_UNTIL: &str = "job-delay-output-until";
+pub const JOB_DELAY_OUTPUT_UNTIL_TIME: &str = "job-delay-output-until-time";
+pub const MEDIA_COL_READY: &str = "media-col-ready";
+pub const PRINTER_INPUT_TRAY: &str = "printer-input-tray";
+pub const PRINTER_OUTPUT_TRAY: &str = "printer-output-tray";
+pub const PRINTER_MANDATORY_JOB_ATTRIBUTES: &str = "printer-mandatory-job-attributes";
+pub const MEDIA_SIZE: &str = "media-size";
+pub const MEDIA_SOURCE: &str = "media-source";
+pub const MEDIA_TYPE: &str = "media-type";
+pub const MEDIA_TOP_MARGIN: &str = "media-top-margin";
+pub const MEDIA_BOTTOM_MARGIN: &str = "media-bottom-margin";
+pub const MEDIA_LEFT_MARGIN: &str = "media-left-margin";
+pub const MEDIA_RIGHT_MARGIN: &str = "media-right-margin";
+pub const X_DIMENSION: &str = "x-dimension";
+pub const Y_DIMENSION: &str = "y-dimension";
+pub const PRINT_COLOR_MODE: &str = "print-color-mode";
+
+// Remove `urn:uuid:` prefix from the UUID value, if any
+pub(crate) fn strip_uuid_urn(uuid: &str) -> &str {
+    match uuid.get(..9) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("urn:uuid:") => &uuid[9..],
+        _ => uuid,
+    }
+}
 
 const HEADER_ATTRS: [&str; 3] = [ATTRIBUTES_CHARSET, ATTRIBUTES_NATURAL_LANGUAGE, PRINTER_URI];
 
@@ -75,7 +170,8 @@ fn is_header_attr(attr: &str) -> bool {
 }
 
 /// `IppAttribute` represents an IPP attribute
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IppAttribute {
     /// Attribute name
     name: String,
@@ -104,6 +200,11 @@ impl IppAttribute {
     pub fn value(&self) -> &IppValue {
         &self.value
     }
+
+    /// Format attribute in IPP text syntax, for example `sides=two-sided-long-edge` or `page-ranges=1-3,5`
+    pub fn to_ipp_syntax(&self) -> String {
+        format!("{}={}", self.name, self.value.to_ipp_syntax())
+    }
 }
 
 impl IppWriter for IppAttribute {
@@ -127,7 +228,8 @@ impl IppWriter for IppAttribute {
 }
 
 /// Attribute group
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IppAttributeGroup {
     tag: DelimiterTag,
     attributes: HashMap<String, IppAttribute>,
@@ -159,15 +261,42 @@ impl IppAttributeGroup {
 }
 
 /// Attribute list
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IppAttributes {
     groups: Vec<IppAttributeGroup>,
+    operation: Option<Operation>,
 }
 
 impl IppAttributes {
     /// Create attribute list
     pub fn new() -> IppAttributes {
-        IppAttributes { groups: Vec::new() }
+        IppAttributes {
+            groups: Vec::new(),
+            operation: None,
+        }
+    }
+
+    /// Parse attribute groups of an encoded IPP request or response without a network call,
+    /// for example from a captured `.ipp` dump. Use `IppRequestResponse::from_reader` to get the header as well
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<IppAttributes, ParseError> {
+        IppParser::new(&mut reader).parse().map(|result| result.attributes)
+    }
+
+    /// Parse attribute groups of an encoded IPP request or response from a byte slice
+    pub fn from_bytes(data: &[u8]) -> Result<IppAttributes, ParseError> {
+        IppAttributes::from_reader(io::Cursor::new(data))
+    }
+
+    /// Get the operation which produced these attributes, if known.
+    /// It is set by the client for the responses returned from `IppClient::send`
+    pub fn operation(&self) -> Option<Operation> {
+        self.operation
+    }
+
+    /// Set the operation which produced these attributes
+    pub fn set_operation(&mut self, operation: Operation) {
+        self.operation = Some(operation);
     }
 
     /// Get all groups
@@ -185,6 +314,126 @@ impl IppAttributes {
         self.groups.iter().filter(|g| g.tag == tag).collect()
     }
 
+    /// Get all attributes with a given name from every group matching a given delimiter tag,
+    /// for example job-id from each job group in a Get-Jobs response
+    pub fn get_all(&self, tag: DelimiterTag, name: &str) -> Vec<&IppAttribute> {
+        self.groups
+            .iter()
+            .filter(|g| g.tag == tag)
+            .filter_map(|g| g.attributes.get(name))
+            .collect()
+    }
+
+    /// Get the first job attributes group, for example from a Get-Job-Attributes or Print-Job response
+    pub fn job_attributes(&self) -> Option<&IppAttributeGroup> {
+        self.groups.iter().find(|g| g.tag == DelimiterTag::JobAttributes)
+    }
+
+    fn printer_attribute(&self, name: &str) -> Option<&IppValue> {
+        self.groups_of(DelimiterTag::PrinterAttributes)
+            .first()
+            .and_then(|g| g.attributes().get(name))
+            .map(|attr| attr.value())
+    }
+
+    /// Return operations-supported attribute as a list of typed operations.
+    /// Operation codes which are not known to this crate are kept as `OperationCode::Unknown`.
+    pub fn operations_supported(&self) -> Vec<OperationCode> {
+        self.printer_attribute(OPERATIONS_SUPPORTED)
+            .map(|value| {
+                value
+                    .into_iter()
+                    .filter_map(|v| v.as_enum())
+                    .filter_map(|v| u16::try_from(*v).ok())
+                    .map(OperationCode::from_code)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Check whether a given operation is listed in operations-supported attribute
+    pub fn supports_operation(&self, operation: Operation) -> bool {
+        self.operations_supported().contains(&OperationCode::Known(operation))
+    }
+
+    /// Return printer-state-reasons attribute as a list of typed reasons with severities.
+    /// The `none` keyword is skipped, so an empty list means that there are no reasons
+    pub fn printer_state_reasons(&self) -> Vec<PrinterStateReason> {
+        self.printer_attribute(PRINTER_STATE_REASONS)
+            .map(|value| {
+                value
+                    .into_iter()
+                    .filter_map(|v| v.as_keyword())
+                    .filter(|k| *k != "none")
+                    .map(|k| PrinterStateReason::parse(k))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Return printer-state-change-time attribute: printer uptime in seconds when the state last changed
+    pub fn printer_state_change_time(&self) -> Option<Duration> {
+        self.printer_attribute(PRINTER_STATE_CHANGE_TIME)
+            .and_then(|v| v.as_integer())
+            .filter(|v| **v >= 0)
+            .map(|v| Duration::from_secs(*v as u64))
+    }
+
+    /// Return printer-impressions-completed attribute as a 64-bit counter, see `IppValue::as_i64`
+    pub fn printer_impressions_completed(&self) -> Option<i64> {
+        self.printer_attribute(PRINTER_IMPRESSIONS_COMPLETED)
+            .and_then(|v| v.as_i64())
+    }
+
+    /// Return printer-more-info attribute: URI of the printer web page, for example the embedded web server
+    pub fn printer_more_info(&self) -> Option<String> {
+        self.printer_attribute(PRINTER_MORE_INFO)
+            .and_then(|v| v.as_uri())
+            .cloned()
+    }
+
+    /// Return printer-supply-info-uri attribute: URI of the page for supply status and ordering
+    pub fn printer_supply_info_uri(&self) -> Option<String> {
+        self.printer_attribute(PRINTER_SUPPLY_INFO_URI)
+            .and_then(|v| v.as_uri())
+            .cloned()
+    }
+
+    /// Return printer-uuid attribute without the `urn:uuid:` prefix.
+    /// Unlike the printer URI, the UUID does not change when the printer address or name changes
+    pub fn printer_uuid(&self) -> Option<String> {
+        self.printer_attribute(PRINTER_UUID)
+            .and_then(|v| v.as_str())
+            .map(|uuid| strip_uuid_urn(uuid).to_owned())
+    }
+
+    /// Return printer-state-change-date-time attribute as DateTime value,
+    /// see `IppValue::to_system_time` and `IppValue::to_chrono` for conversions
+    pub fn printer_state_change_date_time(&self) -> Option<IppValue> {
+        self.printer_attribute(PRINTER_STATE_CHANGE_DATE_TIME)
+            .filter(|v| matches!(v, IppValue::DateTime { .. }))
+            .cloned()
+    }
+
+    /// Return time elapsed since the last printer state change. The printer-state-change-date-time
+    /// attribute is preferred, otherwise printer-state-change-time is compared to printer-up-time
+    pub fn printer_state_change_age(&self) -> Option<Duration> {
+        if let Some(time) = self.printer_state_change_date_time().and_then(|v| v.to_system_time()) {
+            return Some(SystemTime::now().duration_since(time).unwrap_or_default());
+        }
+
+        let up_time = self
+            .printer_attribute(PRINTER_UP_TIME)
+            .and_then(|v| v.as_integer())
+            .filter(|v| **v >= 0)?;
+        let change_time = self.printer_state_change_time()?;
+        Some(
+            Duration::from_secs(*up_time as u64)
+                .checked_sub(change_time)
+                .unwrap_or_default(),
+        )
+    }
+
     /// Add attribute to a given group
     pub fn add(&mut self, tag: DelimiterTag, attribute: IppAttribute) {
         let mut group = self.groups_mut().iter_mut().find(|g| g.tag() == tag);
@@ -242,3 +491,342 @@ impl IppWriter for IppAttributes {
         Ok(retval)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use super::*;
+
+    #[test]
+    fn test_printer_state_reasons() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_STATE_REASONS,
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("media-jam-error".to_owned()),
+                    IppValue::Keyword("toner-low-warning".to_owned()),
+                    IppValue::Keyword("com.example-service-due-report".to_owned()),
+                ]),
+            ),
+        );
+
+        let reasons = attrs.printer_state_reasons();
+        assert_eq!(
+            reasons,
+            vec![
+                PrinterStateReason {
+                    reason: StateReason::MediaJam,
+                    severity: StateReasonSeverity::Error,
+                },
+                PrinterStateReason {
+                    reason: StateReason::TonerLow,
+                    severity: StateReasonSeverity::Warning,
+                },
+                PrinterStateReason {
+                    reason: StateReason::Unknown("com.example-service-due".to_owned()),
+                    severity: StateReasonSeverity::Report,
+                },
+            ]
+        );
+        assert!(reasons[0].is_error());
+        assert!(!reasons[1].is_error());
+
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_STATE_REASONS, IppValue::Keyword("cover-open".to_owned())),
+        );
+        let reasons = attrs.printer_state_reasons();
+        assert_eq!(reasons[0].reason, StateReason::CoverOpen);
+        assert!(reasons[0].is_error());
+
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_STATE_REASONS, IppValue::Keyword("none".to_owned())),
+        );
+        assert!(attrs.printer_state_reasons().is_empty());
+    }
+
+    #[test]
+    fn test_operations_supported() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                OPERATIONS_SUPPORTED,
+                IppValue::ListOf(vec![
+                    IppValue::Enum(Operation::PrintJob as i32),
+                    IppValue::Enum(0x7777),
+                    IppValue::Enum(Operation::GetPrinterAttributes as i32),
+                ]),
+            ),
+        );
+        assert_eq!(
+            attrs.operations_supported(),
+            vec![
+                OperationCode::Known(Operation::PrintJob),
+                OperationCode::Unknown(0x7777),
+                OperationCode::Known(Operation::GetPrinterAttributes)
+            ]
+        );
+        assert!(attrs.supports_operation(Operation::PrintJob));
+        assert!(!attrs.supports_operation(Operation::CreateJob));
+    }
+
+    #[test]
+    fn test_printer_state_change_time() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_STATE_CHANGE_TIME, IppValue::Integer(100)),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_UP_TIME, IppValue::Integer(160)),
+        );
+        assert_eq!(attrs.printer_state_change_time(), Some(Duration::from_secs(100)));
+        assert_eq!(attrs.printer_state_change_date_time(), None);
+        assert_eq!(attrs.printer_state_change_age(), Some(Duration::from_secs(60)));
+
+        let time = UNIX_EPOCH + Duration::from_secs(1_500_000_000);
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_STATE_CHANGE_DATE_TIME, IppValue::from(time)),
+        );
+        assert_eq!(attrs.printer_state_change_date_time(), Some(IppValue::from(time)));
+        assert!(attrs.printer_state_change_age().unwrap() > Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_printer_state_change_time_negative() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_STATE_CHANGE_TIME, IppValue::Integer(-1)),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_UP_TIME, IppValue::Integer(160)),
+        );
+        assert_eq!(attrs.printer_state_change_time(), None);
+        assert_eq!(attrs.printer_state_change_age(), None);
+    }
+
+    #[test]
+    fn test_to_ipp_syntax() {
+        let attr = IppAttribute::new(SIDES, IppValue::Keyword("two-sided-long-edge".to_owned()));
+        assert_eq!(attr.to_ipp_syntax(), "sides=two-sided-long-edge");
+
+        let attr = IppAttribute::new(
+            "page-ranges",
+            IppValue::ListOf(vec![
+                IppValue::RangeOfInteger { min: 1, max: 3 },
+                IppValue::RangeOfInteger { min: 5, max: 5 },
+            ]),
+        );
+        assert_eq!(attr.to_ipp_syntax(), "page-ranges=1-3,5");
+
+        let attr = IppAttribute::new(
+            "printer-resolution",
+            IppValue::Resolution {
+                crossfeed: 600,
+                feed: 600,
+                units: 3,
+            },
+        );
+        assert_eq!(attr.to_ipp_syntax(), "printer-resolution=600x600dpi");
+
+        let attr = IppAttribute::new(JOB_NAME, IppValue::NameWithoutLanguage("my report".to_owned()));
+        assert_eq!(attr.to_ipp_syntax(), "job-name=\"my report\"");
+
+        let attr = IppAttribute::new(
+            "media-col",
+            IppValue::collection(vec![
+                (
+                    "media-size",
+                    IppValue::collection(vec![
+                        ("x-dimension", IppValue::Integer(21000)),
+                        ("y-dimension", IppValue::Integer(29700)),
+                    ]),
+                ),
+                ("media-type", IppValue::Keyword("stationery".to_owned())),
+            ]),
+        );
+        assert_eq!(
+            attr.to_ipp_syntax(),
+            "media-col={media-size={x-dimension=21000 y-dimension=29700} media-type=stationery}"
+        );
+    }
+
+    #[test]
+    fn test_get_all() {
+        let mut attrs = IppAttributes::new();
+        for id in 1..=3 {
+            let mut group = IppAttributeGroup::new(DelimiterTag::JobAttributes);
+            group
+                .attributes_mut()
+                .insert(JOB_ID.to_owned(), IppAttribute::new(JOB_ID, IppValue::Integer(id)));
+            attrs.groups_mut().push(group);
+        }
+        attrs.add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(JOB_ID, IppValue::Integer(100)),
+        );
+
+        let ids = attrs
+            .get_all(DelimiterTag::JobAttributes, JOB_ID)
+            .into_iter()
+            .map(|a| a.value().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![IppValue::Integer(1), IppValue::Integer(2), IppValue::Integer(3)]
+        );
+        assert!(attrs.get_all(DelimiterTag::JobAttributes, JOB_NAME).is_empty());
+    }
+
+    #[test]
+    fn test_printer_uuid() {
+        let mut attrs = IppAttributes::new();
+        assert_eq!(attrs.printer_uuid(), None);
+
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_UUID,
+                IppValue::Uri("urn:uuid:4509a320-00a0-008f-00b6-002507510eca".to_owned()),
+            ),
+        );
+        assert_eq!(
+            attrs.printer_uuid(),
+            Some("4509a320-00a0-008f-00b6-002507510eca".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_printer_info_links() {
+        let mut attrs = IppAttributes::new();
+        assert_eq!(attrs.printer_more_info(), None);
+
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_MORE_INFO, IppValue::Uri("http://printer/".to_owned())),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_SUPPLY_INFO_URI,
+                IppValue::Uri("http://printer/supplies".to_owned()),
+            ),
+        );
+        assert_eq!(attrs.printer_more_info(), Some("http://printer/".to_owned()));
+        assert_eq!(
+            attrs.printer_supply_info_uri(),
+            Some("http://printer/supplies".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_printer_impressions_completed() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_IMPRESSIONS_COMPLETED,
+                IppValue::OctetString("5000000000".to_owned()),
+            ),
+        );
+        assert_eq!(attrs.printer_impressions_completed(), Some(5_000_000_000));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        use num_traits::FromPrimitive;
+
+        use crate::{ipp::StatusCode, IppHeader, IppVersion};
+
+        let header = IppHeader::new(
+            IppVersion::Ipp20,
+            StatusCode::SuccessfulOKIgnoredOrSubstitutedAttributes as u16,
+            7,
+        );
+
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(ATTRIBUTES_CHARSET, IppValue::Charset("utf-8".to_owned())),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_STATE, IppValue::Enum(3)),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                MEDIA_COL_READY,
+                IppValue::ListOf(vec![IppValue::collection(vec![
+                    (MEDIA_SOURCE, IppValue::Keyword("tray-1".to_owned())),
+                    (MEDIA_TOP_MARGIN, IppValue::Integer(300)),
+                ])]),
+            ),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_STATE_CHANGE_DATE_TIME, IppValue::from(UNIX_EPOCH)),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new("copies-supported", IppValue::RangeOfInteger { min: 1, max: 99 }),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                "printer-resolution-default",
+                IppValue::Resolution {
+                    crossfeed: 600,
+                    feed: 600,
+                    units: 3,
+                },
+            ),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                "vendor-data",
+                IppValue::Other {
+                    tag: 0x7f,
+                    data: bytes::Bytes::from(vec![1, 2, 3]),
+                },
+            ),
+        );
+        attrs.add(
+            DelimiterTag::UnsupportedAttributes,
+            IppAttribute::new("finishings", IppValue::Enum(4)),
+        );
+        attrs.set_operation(Operation::GetPrinterAttributes);
+
+        let value = serde_json::to_value((&header, &attrs)).unwrap();
+        let (new_header, new_attrs): (IppHeader, IppAttributes) = serde_json::from_value(value).unwrap();
+
+        assert_eq!(new_header, header);
+        assert_eq!(
+            StatusCode::from_u16(new_header.operation_status),
+            Some(StatusCode::SuccessfulOKIgnoredOrSubstitutedAttributes)
+        );
+        assert_eq!(new_attrs, attrs);
+        assert_eq!(new_attrs.operation(), Some(Operation::GetPrinterAttributes));
+        assert_eq!(
+            new_attrs.groups().iter().map(|g| g.tag()).collect::<Vec<_>>(),
+            vec![
+                DelimiterTag::OperationAttributes,
+                DelimiterTag::PrinterAttributes,
+                DelimiterTag::UnsupportedAttributes
+            ]
+        );
+    }
+}
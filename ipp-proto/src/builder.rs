@@ -1,9 +1,307 @@
+use std::{
+    cmp,
+    io::Write,
+    time::{Duration, SystemTime},
+};
+
 use crate::{
-    attribute::IppAttribute,
-    operation::{CreateJob, GetPrinterAttributes, IppOperation, PrintJob, SendDocument},
-    IppJobSource,
+    attribute::*,
+    finishings::FinishingsCol,
+    ipp::{IppKeyword, PrintColorMode, RequestedAttribute, Sides},
+    media::MediaCol,
+    operation::{
+        cups::CupsGetPrinters, CreateJob, GetJobAttributes, GetJobs, GetPrinterAttributes, IppOperation,
+        NaturalLanguageOverride, PausePrinter, PrintJob, PrintUri, ResumePrinter, SendDocument, SetJobAttributes,
+        SetPrinterAttributes, ValidateJob,
+    },
+    parser::ParseError,
+    IppJobSource, IppValue, TransferMode,
 };
 
+// Setter of attributes-natural-language shared by the operation builders. The builder keeps
+// the value in `natural_language` field and wraps the operation in NaturalLanguageOverride in `build`
+macro_rules! natural_language_setter {
+    () => {
+        /// Specify attributes-natural-language operation attribute for this request, for example `de`.
+        /// Overrides the natural language configured for the client.
+        pub fn natural_language(mut self, language: &str) -> Self {
+            self.natural_language = Some(language.to_owned());
+            self
+        }
+    };
+}
+
+// Setters of job-error-sheet and separator-sheets collections shared by the job template setters
+// and Create-Job builder. The builder adds them with its `attribute` method
+macro_rules! sheet_setters {
+    () => {
+        /// Specify job-error-sheet collection attribute with job-error-sheet-type (for example `standard`)
+        /// and optional job-error-sheet-when (`always` or `on-error`)
+        pub fn job_error_sheet(self, sheet_type: &str, when: Option<&str>) -> Self {
+            self.attribute(job_error_sheet(sheet_type, when))
+        }
+
+        /// Specify separator-sheets collection attribute with separator-sheets-type (for example `slip-sheets`)
+        /// and optional media for the separator sheets
+        pub fn separator_sheets(self, sheets_type: &str, media: Option<&str>) -> Self {
+            self.attribute(separator_sheets(sheets_type, media))
+        }
+    };
+}
+
+// Job template and job description setters shared by Print-Job, Print-URI and Validate-Job builders.
+// The builder keeps them in `user_name`, `job_title`, `attributes` and `operation_attributes` fields
+macro_rules! job_template_setters {
+    () => {
+        /// Specify requesting-user-name attribute
+        pub fn user_name(mut self, user_name: &str) -> Self {
+            self.user_name = Some(user_name.to_owned());
+            self
+        }
+
+        /// Specify job-name attribute
+        pub fn job_title(mut self, job_title: &str) -> Self {
+            self.job_title = Some(job_title.to_owned());
+            self
+        }
+
+        /// Specify sides attribute
+        pub fn sides(self, sides: Sides) -> Self {
+            self.keyword(SIDES, &sides)
+        }
+
+        /// Specify print-color-mode attribute
+        pub fn print_color_mode(self, color_mode: PrintColorMode) -> Self {
+            self.keyword(PRINT_COLOR_MODE, &color_mode)
+        }
+
+        /// Specify printer-resolution attribute. `units` is 3 for dots per inch or 4 for dots per centimeter
+        pub fn printer_resolution(self, crossfeed: i32, feed: i32, units: i8) -> Self {
+            self.attribute(IppAttribute::new(
+                PRINTER_RESOLUTION,
+                IppValue::Resolution {
+                    crossfeed,
+                    feed,
+                    units,
+                },
+            ))
+        }
+
+        /// Specify media attribute, for example `iso_a4_210x297mm`
+        pub fn media(self, media: &str) -> Self {
+            self.attribute(IppAttribute::new(MEDIA, IppValue::Keyword(media.to_owned())))
+        }
+
+        /// Specify media-col attribute, see `MediaColBuilder`
+        pub fn media_col(self, media: MediaCol) -> Self {
+            self.attribute(IppAttribute::new(MEDIA_COL, media.to_value()))
+        }
+
+        /// Specify finishings-col attribute, see `FinishingsColBuilder`
+        pub fn finishings_col(self, finishings: FinishingsCol) -> Self {
+            self.attribute(IppAttribute::new(FINISHINGS_COL, finishings.to_value()))
+        }
+
+        /// Specify page-ranges attribute as a list of (first, last) page pairs
+        pub fn page_ranges(self, ranges: &[(u32, u32)]) -> Self {
+            self.attribute(IppAttribute::new(
+                PAGE_RANGES,
+                IppValue::ListOf(
+                    ranges
+                        .iter()
+                        .map(|&(min, max)| IppValue::RangeOfInteger {
+                            min: min as i32,
+                            max: max as i32,
+                        })
+                        .collect(),
+                ),
+            ))
+        }
+
+        /// Specify page-ranges attribute using lp-style syntax, for example `1-3,5,8-10`.
+        /// A single page `5` is treated as `5-5`.
+        pub fn page_ranges_str(self, ranges: &str) -> Result<Self, ParseError> {
+            let ranges = parse_page_ranges(ranges)?;
+            Ok(self.page_ranges(&ranges))
+        }
+
+        /// Specify output-device-uuid attribute to target a specific output device in IPP infrastructure printing.
+        /// The UUID can be given with or without `urn:uuid:` prefix
+        pub fn output_device_uuid(self, uuid: &str) -> Result<Self, ParseError> {
+            let uuid = parse_uuid_urn(uuid)?;
+            Ok(self.attribute(IppAttribute::new(OUTPUT_DEVICE_UUID, IppValue::Uri(uuid))))
+        }
+
+        /// Specify client-supplied job-uuid attribute. Reusing the same UUID when re-submitting a job
+        /// allows to detect duplicates with `JobInfo::uuid`. The UUID can be given with or without `urn:uuid:` prefix
+        pub fn job_uuid(self, uuid: &str) -> Result<Self, ParseError> {
+            let uuid = parse_uuid_urn(uuid)?;
+            Ok(self.attribute(IppAttribute::new(JOB_UUID, IppValue::Uri(uuid))))
+        }
+
+        /// Specify job-sheets attribute, for example `none` or `standard`
+        pub fn job_sheets(self, job_sheets: &str) -> Self {
+            self.attribute(IppAttribute::new(
+                JOB_SHEETS,
+                IppValue::Keyword(job_sheets.to_owned()),
+            ))
+        }
+
+        /// Specify presentation-direction-number-up attribute: the page order for number-up layouts,
+        /// for example `toright-tobottom`
+        pub fn presentation_direction(self, direction: &str) -> Self {
+            self.attribute(IppAttribute::new(
+                PRESENTATION_DIRECTION_NUMBER_UP,
+                IppValue::Keyword(direction.to_owned()),
+            ))
+        }
+
+        /// Specify imposition-template attribute, for example `booklet` or `none`
+        pub fn imposition_template(self, template: &str) -> Self {
+            self.attribute(IppAttribute::new(
+                IMPOSITION_TEMPLATE,
+                IppValue::Keyword(template.to_owned()),
+            ))
+        }
+
+        /// Specify job-sheets attribute with separate start and end banner sheets (CUPS extension)
+        pub fn job_sheets_start_end(self, start: &str, end: &str) -> Self {
+            self.attribute(IppAttribute::new(
+                JOB_SHEETS,
+                IppValue::ListOf(vec![
+                    IppValue::Keyword(start.to_owned()),
+                    IppValue::Keyword(end.to_owned()),
+                ]),
+            ))
+        }
+
+        /// Specify job-sheets-col collection attribute with optional media for the banner sheets
+        pub fn job_sheets_col(self, job_sheets: &str, media: Option<&str>) -> Self {
+            let mut members = vec![(JOB_SHEETS, IppValue::Keyword(job_sheets.to_owned()))];
+            if let Some(media) = media {
+                members.push((MEDIA, IppValue::Keyword(media.to_owned())));
+            }
+            self.attribute(IppAttribute::new(JOB_SHEETS_COL, IppValue::collection(members)))
+        }
+
+        sheet_setters!();
+
+        /// Specify custom keyword job attribute using a type which implements `IppKeyword`
+        pub fn keyword<K>(self, name: &str, keyword: &K) -> Self
+        where
+            K: IppKeyword,
+        {
+            self.attribute(IppAttribute::new(name, keyword.to_value()))
+        }
+
+        /// Specify document-format-details operation attribute, see `DocumentFormatDetailsBuilder`
+        pub fn document_format_details(self, details: IppValue) -> Self {
+            self.operation_attribute(IppAttribute::new(DOCUMENT_FORMAT_DETAILS, details))
+        }
+
+        /// Specify document-charset operation attribute for plain text documents, for example `utf-8`
+        pub fn document_charset(self, charset: &str) -> Self {
+            self.operation_attribute(IppAttribute::new(
+                DOCUMENT_CHARSET,
+                IppValue::Charset(charset.to_ascii_lowercase()),
+            ))
+        }
+
+        /// Specify job-mandatory-attributes operation attribute: a list of job attributes
+        /// which the printer must not ignore or substitute
+        pub fn mandatory_attributes(self, names: &[&str]) -> Self {
+            let names = names.iter().map(|n| IppValue::Keyword((*n).to_owned())).collect();
+            self.operation_attribute(IppAttribute::new(
+                JOB_MANDATORY_ATTRIBUTES,
+                IppValue::ListOf(names),
+            ))
+        }
+
+        /// Specify ipp-attribute-fidelity operation attribute. If true the job is rejected
+        /// when any of the job attributes is not supported
+        pub fn attribute_fidelity(self, fidelity: bool) -> Self {
+            self.operation_attribute(IppAttribute::new(
+                IPP_ATTRIBUTE_FIDELITY,
+                IppValue::Boolean(fidelity),
+            ))
+        }
+
+        /// Specify custom job attribute
+        pub fn attribute(mut self, attribute: IppAttribute) -> Self {
+            self.attributes.push(attribute);
+            self
+        }
+
+        /// Specify custom operation attribute
+        pub fn operation_attribute(mut self, attribute: IppAttribute) -> Self {
+            self.operation_attributes.push(attribute);
+            self
+        }
+    };
+}
+
+fn parse_page(page: &str, ranges: &str) -> Result<u32, ParseError> {
+    match page.trim().parse::<u32>() {
+        Ok(page) if page > 0 && page <= i32::MAX as u32 => Ok(page),
+        _ => Err(ParseError::InvalidValue(ranges.to_owned())),
+    }
+}
+
+// Parse lp-style page ranges, for example 1-3,5,8-10
+fn parse_page_ranges(ranges: &str) -> Result<Vec<(u32, u32)>, ParseError> {
+    ranges
+        .split(',')
+        .map(|range| {
+            let (min, max) = match range.find('-') {
+                Some(pos) => (
+                    parse_page(&range[..pos], ranges)?,
+                    parse_page(&range[pos + 1..], ranges)?,
+                ),
+                None => {
+                    let page = parse_page(range, ranges)?;
+                    (page, page)
+                }
+            };
+            if min > max {
+                Err(ParseError::InvalidValue(ranges.to_owned()))
+            } else {
+                Ok((min, max))
+            }
+        })
+        .collect()
+}
+
+// job-error-sheet collection, `when` is a job-error-sheet-when keyword: `always` or `on-error`
+fn job_error_sheet(sheet_type: &str, when: Option<&str>) -> IppAttribute {
+    let mut members = vec![(JOB_ERROR_SHEET_TYPE, IppValue::Keyword(sheet_type.to_owned()))];
+    if let Some(when) = when {
+        members.push((JOB_ERROR_SHEET_WHEN, IppValue::Keyword(when.to_owned())));
+    }
+    IppAttribute::new(JOB_ERROR_SHEET, IppValue::collection(members))
+}
+
+fn separator_sheets(sheets_type: &str, media: Option<&str>) -> IppAttribute {
+    let mut members = vec![(SEPARATOR_SHEETS_TYPE, IppValue::Keyword(sheets_type.to_owned()))];
+    if let Some(media) = media {
+        members.push((MEDIA, IppValue::Keyword(media.to_owned())));
+    }
+    IppAttribute::new(SEPARATOR_SHEETS, IppValue::collection(members))
+}
+
+// Convert UUID with or without urn:uuid: prefix into the urn:uuid: form
+fn parse_uuid_urn(uuid: &str) -> Result<String, ParseError> {
+    let bare = strip_uuid_urn(uuid);
+
+    let valid = bare.split('-').map(str::len).eq([8, 4, 4, 4, 12].iter().cloned())
+        && bare.chars().all(|c| c == '-' || c.is_ascii_hexdigit());
+
+    if valid {
+        Ok(format!("urn:uuid:{}", bare.to_ascii_lowercase()))
+    } else {
+        Err(ParseError::InvalidValue(uuid.to_owned()))
+    }
+}
+
 /// Builder to create IPP operations
 pub struct IppOperationBuilder;
 
@@ -18,6 +316,13 @@ impl IppOperationBuilder {
         PrintJobBuilder::new(source.into())
     }
 
+    /// Create Print-URI operation. The printer fetches the document from the given URI itself
+    ///
+    /// * `uri` - document URI, for example `https://example.com/report.pdf`
+    pub fn print_uri(uri: &str) -> PrintUriBuilder {
+        PrintUriBuilder::new(uri)
+    }
+
     /// Create GetPrinterAttributes operation
     pub fn get_printer_attributes() -> GetPrinterAttributesBuilder {
         GetPrinterAttributesBuilder::new()
@@ -38,6 +343,50 @@ impl IppOperationBuilder {
     {
         SendDocumentBuilder::new(job_id, source.into())
     }
+
+    /// Create CUPS-Get-Printers operation
+    pub fn cups_get_printers() -> CupsGetPrintersBuilder {
+        CupsGetPrintersBuilder::new()
+    }
+
+    /// Create Set-Printer-Attributes operation
+    pub fn set_printer_attributes() -> SetPrinterAttributesBuilder {
+        SetPrinterAttributesBuilder::new()
+    }
+
+    /// Create Set-Job-Attributes operation
+    ///
+    /// * `job_id` - id of the job to modify
+    pub fn set_job_attributes(job_id: i32) -> SetJobAttributesBuilder {
+        SetJobAttributesBuilder::new(job_id)
+    }
+
+    /// Create Get-Jobs operation
+    pub fn get_jobs() -> GetJobsBuilder {
+        GetJobsBuilder::new()
+    }
+
+    /// Create Get-Job-Attributes operation
+    ///
+    /// * `job_id` - id of the job to query
+    pub fn get_job_attributes(job_id: i32) -> GetJobAttributesBuilder {
+        GetJobAttributesBuilder::new(job_id)
+    }
+
+    /// Create Validate-Job operation to check job attributes without printing
+    pub fn validate_job() -> ValidateJobBuilder {
+        ValidateJobBuilder::new()
+    }
+
+    /// Create Pause-Printer operation. Requires operator privileges on most printers
+    pub fn pause_printer() -> PausePrinterBuilder {
+        PausePrinterBuilder::new()
+    }
+
+    /// Create Resume-Printer operation. Requires operator privileges on most printers
+    pub fn resume_printer() -> ResumePrinterBuilder {
+        ResumePrinterBuilder::new()
+    }
 }
 
 /// Builder to create PrintJob operation
@@ -46,6 +395,10 @@ pub struct PrintJobBuilder {
     user_name: Option<String>,
     job_title: Option<String>,
     attributes: Vec<IppAttribute>,
+    operation_attributes: Vec<IppAttribute>,
+    natural_language: Option<String>,
+    tee: Option<Box<dyn Write + Send>>,
+    detect_format: bool,
 }
 
 impl PrintJobBuilder {
@@ -55,44 +408,203 @@ impl PrintJobBuilder {
             user_name: None,
             job_title: None,
             attributes: Vec::new(),
+            operation_attributes: Vec::new(),
+            natural_language: None,
+            tee: None,
+            detect_format: false,
         }
     }
-    /// Specify requesting-user-name attribute
-    pub fn user_name(mut self, user_name: &str) -> Self {
-        self.user_name = Some(user_name.to_owned());
+    /// Write a copy of the full request body, including the document, to the given writer
+    /// as it is streamed to the printer. Stream sources are not buffered in memory
+    pub fn tee_to<W>(mut self, writer: W) -> Self
+    where
+        W: Write + Send + 'static,
+    {
+        self.tee = Some(Box::new(writer));
         self
     }
 
-    /// Specify job-name attribute
-    pub fn job_title(mut self, job_title: &str) -> Self {
-        self.job_title = Some(job_title.to_owned());
+    /// Specify HTTP transfer mode for the document data.
+    /// Default is `TransferMode::Buffered` for in-memory sources and `TransferMode::Chunked` for readers
+    pub fn transfer_mode(mut self, mode: TransferMode) -> Self {
+        self.source.set_transfer_mode(mode);
         self
     }
 
-    /// Specify custom job attribute
-    pub fn attribute(mut self, attribute: IppAttribute) -> Self {
-        self.attributes.push(attribute);
+    /// Detect document-format operation attribute from the first bytes of the document
+    /// if it is not specified explicitly. PDF, JPEG and PostScript documents are recognized,
+    /// other documents are sent as `application/octet-stream`. Default is false
+    pub fn detect_format(mut self, detect: bool) -> Self {
+        self.detect_format = detect;
+        self
+    }
+
+    /// Specify callback which is invoked with the cumulative number of document bytes
+    /// as the document is streamed, see `IppJobSource::set_progress_callback`
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(u64) + Send + 'static,
+    {
+        self.source.set_progress_callback(callback);
         self
     }
 
+    job_template_setters!();
+
+    natural_language_setter!();
+
     /// Build operation
     pub fn build(self) -> impl IppOperation {
-        let op = PrintJob::new(self.source, self.user_name.as_ref(), self.job_title.as_ref());
-        self.attributes.into_iter().fold(op, |mut op, attr| {
+        let mut op = PrintJob::new(self.source, self.user_name.as_ref(), self.job_title.as_ref());
+        for attr in self.operation_attributes {
+            op.add_operation_attribute(attr);
+        }
+        if let Some(tee) = self.tee {
+            op.set_tee(tee);
+        }
+        op.set_detect_format(self.detect_format);
+        let op = self.attributes.into_iter().fold(op, |mut op, attr| {
             op.add_attribute(attr);
             op
-        })
+        });
+        NaturalLanguageOverride::new(op, self.natural_language)
+    }
+}
+
+/// Builder to create Print-URI operation
+pub struct PrintUriBuilder {
+    document_uri: String,
+    user_name: Option<String>,
+    job_title: Option<String>,
+    attributes: Vec<IppAttribute>,
+    operation_attributes: Vec<IppAttribute>,
+    natural_language: Option<String>,
+}
+
+impl PrintUriBuilder {
+    fn new(document_uri: &str) -> PrintUriBuilder {
+        PrintUriBuilder {
+            document_uri: document_uri.to_owned(),
+            user_name: None,
+            job_title: None,
+            attributes: Vec::new(),
+            operation_attributes: Vec::new(),
+            natural_language: None,
+        }
+    }
+
+    /// Specify document-format operation attribute, for example `application/pdf`.
+    /// Without it the printer detects the format of the fetched document
+    pub fn document_format(self, format: &str) -> Self {
+        self.operation_attribute(IppAttribute::new(
+            DOCUMENT_FORMAT,
+            IppValue::MimeMediaType(format.to_owned()),
+        ))
+    }
+
+    job_template_setters!();
+
+    natural_language_setter!();
+
+    /// Build operation
+    pub fn build(self) -> impl IppOperation {
+        let mut op = PrintUri::new(&self.document_uri, self.user_name.as_ref(), self.job_title.as_ref());
+        for attr in self.operation_attributes {
+            op.add_operation_attribute(attr);
+        }
+        let op = self.attributes.into_iter().fold(op, |mut op, attr| {
+            op.add_attribute(attr);
+            op
+        });
+        NaturalLanguageOverride::new(op, self.natural_language)
+    }
+}
+
+/// Builder to create document-format-details collection
+#[derive(Default)]
+pub struct DocumentFormatDetailsBuilder {
+    members: Vec<(String, IppValue)>,
+}
+
+impl DocumentFormatDetailsBuilder {
+    /// Create document-format-details builder
+    pub fn new() -> DocumentFormatDetailsBuilder {
+        DocumentFormatDetailsBuilder::default()
+    }
+
+    fn member(mut self, name: &str, value: IppValue) -> Self {
+        self.members.push((name.to_owned(), value));
+        self
+    }
+
+    /// Specify document-format member
+    pub fn document_format(self, format: &str) -> Self {
+        self.member(DOCUMENT_FORMAT, IppValue::MimeMediaType(format.to_owned()))
+    }
+
+    /// Specify document-format-version member
+    pub fn document_format_version(self, version: &str) -> Self {
+        self.member(
+            DOCUMENT_FORMAT_VERSION,
+            IppValue::TextWithoutLanguage(version.to_owned()),
+        )
+    }
+
+    /// Specify document-natural-language member
+    pub fn document_natural_language(self, language: &str) -> Self {
+        self.member(
+            DOCUMENT_NATURAL_LANGUAGE,
+            IppValue::NaturalLanguage(language.to_owned()),
+        )
+    }
+
+    /// Specify document-source-application-name member
+    pub fn source_application_name(self, name: &str) -> Self {
+        self.member(
+            DOCUMENT_SOURCE_APPLICATION_NAME,
+            IppValue::NameWithoutLanguage(name.to_owned()),
+        )
+    }
+
+    /// Specify document-source-application-version member
+    pub fn source_application_version(self, version: &str) -> Self {
+        self.member(
+            DOCUMENT_SOURCE_APPLICATION_VERSION,
+            IppValue::TextWithoutLanguage(version.to_owned()),
+        )
+    }
+
+    /// Specify document-source-os-name member
+    pub fn source_os_name(self, name: &str) -> Self {
+        self.member(DOCUMENT_SOURCE_OS_NAME, IppValue::NameWithoutLanguage(name.to_owned()))
+    }
+
+    /// Specify document-source-os-version member
+    pub fn source_os_version(self, version: &str) -> Self {
+        self.member(
+            DOCUMENT_SOURCE_OS_VERSION,
+            IppValue::TextWithoutLanguage(version.to_owned()),
+        )
+    }
+
+    /// Build collection value
+    pub fn build(self) -> IppValue {
+        IppValue::collection(self.members)
     }
 }
 
 /// Builder to create GetPrinterAttributes operation
 pub struct GetPrinterAttributesBuilder {
     attributes: Vec<String>,
+    natural_language: Option<String>,
 }
 
 impl GetPrinterAttributesBuilder {
     fn new() -> GetPrinterAttributesBuilder {
-        GetPrinterAttributesBuilder { attributes: Vec::new() }
+        GetPrinterAttributesBuilder {
+            attributes: Vec::new(),
+            natural_language: None,
+        }
     }
 
     /// Specify which attribute to retrieve from the printer. Can be repeated.
@@ -111,9 +623,20 @@ impl GetPrinterAttributesBuilder {
         self
     }
 
+    /// Specify which attribute or attribute group to retrieve using a typed name. Can be repeated.
+    /// Use `attribute` for the names which are not covered by `RequestedAttribute`
+    pub fn request(self, attribute: RequestedAttribute) -> Self {
+        self.attribute(attribute.to_keyword())
+    }
+
+    natural_language_setter!();
+
     /// Build operation
     pub fn build(self) -> impl IppOperation {
-        GetPrinterAttributes::with_attributes(&self.attributes)
+        NaturalLanguageOverride::new(
+            GetPrinterAttributes::with_attributes(&self.attributes),
+            self.natural_language,
+        )
     }
 }
 
@@ -121,6 +644,7 @@ impl GetPrinterAttributesBuilder {
 pub struct CreateJobBuilder {
     job_name: Option<String>,
     attributes: Vec<IppAttribute>,
+    natural_language: Option<String>,
 }
 
 impl CreateJobBuilder {
@@ -128,6 +652,7 @@ impl CreateJobBuilder {
         CreateJobBuilder {
             job_name: None,
             attributes: Vec::new(),
+            natural_language: None,
         }
     }
 
@@ -137,19 +662,53 @@ impl CreateJobBuilder {
         self
     }
 
+    sheet_setters!();
+
+    /// Specify job-cancel-after attribute. The printer cancels the job if it is still processing
+    /// after the given time. The duration is sent in whole seconds.
+    pub fn cancel_after(self, duration: Duration) -> Self {
+        let seconds = cmp::min(duration.as_secs(), i32::MAX as u64) as i32;
+        self.attribute(IppAttribute::new(JOB_CANCEL_AFTER, IppValue::Integer(seconds)))
+    }
+
+    /// Specify job-retain-until attribute, for example `end-of-day`
+    pub fn retain_until(self, retain_until: &str) -> Self {
+        self.attribute(IppAttribute::new(
+            JOB_RETAIN_UNTIL,
+            IppValue::Keyword(retain_until.to_owned()),
+        ))
+    }
+
+    /// Specify job-delay-output-until attribute: the job is processed but its output is held
+    /// until the given period, for example `night` or `weekend`
+    pub fn delay_output_until(self, delay_output_until: &str) -> Self {
+        self.attribute(IppAttribute::new(
+            JOB_DELAY_OUTPUT_UNTIL,
+            IppValue::Keyword(delay_output_until.to_owned()),
+        ))
+    }
+
+    /// Specify job-delay-output-until-time attribute: the output of the job is held until the given time
+    pub fn delay_output_until_time(self, time: SystemTime) -> Self {
+        self.attribute(IppAttribute::new(JOB_DELAY_OUTPUT_UNTIL_TIME, IppValue::from(time)))
+    }
+
     /// Specify custom job attribute
     pub fn attribute(mut self, attribute: IppAttribute) -> Self {
         self.attributes.push(attribute);
         self
     }
 
+    natural_language_setter!();
+
     /// Build operation
     pub fn build(self) -> impl IppOperation {
         let op = CreateJob::new(self.job_name.as_ref());
-        self.attributes.into_iter().fold(op, |mut op, attr| {
+        let op = self.attributes.into_iter().fold(op, |mut op, attr| {
             op.add_attribute(attr);
             op
-        })
+        });
+        NaturalLanguageOverride::new(op, self.natural_language)
     }
 }
 
@@ -159,6 +718,8 @@ pub struct SendDocumentBuilder {
     source: IppJobSource,
     user_name: Option<String>,
     is_last: bool,
+    operation_attributes: Vec<IppAttribute>,
+    natural_language: Option<String>,
 }
 
 impl SendDocumentBuilder {
@@ -168,6 +729,8 @@ impl SendDocumentBuilder {
             source,
             user_name: None,
             is_last: true,
+            operation_attributes: Vec::new(),
+            natural_language: None,
         }
     }
 
@@ -183,8 +746,1300 @@ impl SendDocumentBuilder {
         self
     }
 
+    /// Specify HTTP transfer mode for the document data.
+    /// Default is `TransferMode::Buffered` for in-memory sources and `TransferMode::Chunked` for readers
+    pub fn transfer_mode(mut self, mode: TransferMode) -> Self {
+        self.source.set_transfer_mode(mode);
+        self
+    }
+
+    /// Specify callback which is invoked with the cumulative number of document bytes
+    /// as the document is streamed, see `IppJobSource::set_progress_callback`
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(u64) + Send + 'static,
+    {
+        self.source.set_progress_callback(callback);
+        self
+    }
+
+    /// Specify document-charset operation attribute for plain text documents, for example `utf-8`
+    pub fn document_charset(self, charset: &str) -> Self {
+        self.operation_attribute(IppAttribute::new(
+            DOCUMENT_CHARSET,
+            IppValue::Charset(charset.to_ascii_lowercase()),
+        ))
+    }
+
+    /// Specify custom operation attribute
+    pub fn operation_attribute(mut self, attribute: IppAttribute) -> Self {
+        self.operation_attributes.push(attribute);
+        self
+    }
+
+    natural_language_setter!();
+
+    /// Build operation
+    pub fn build(self) -> impl IppOperation {
+        let mut op = SendDocument::new(self.job_id, self.source, self.user_name.as_ref(), self.is_last);
+        for attr in self.operation_attributes {
+            op.add_operation_attribute(attr);
+        }
+        NaturalLanguageOverride::new(op, self.natural_language)
+    }
+}
+
+/// Builder to create CUPS-Get-Printers operation
+pub struct CupsGetPrintersBuilder {
+    first_printer_name: Option<String>,
+    limit: Option<i32>,
+    natural_language: Option<String>,
+}
+
+impl CupsGetPrintersBuilder {
+    fn new() -> CupsGetPrintersBuilder {
+        CupsGetPrintersBuilder {
+            first_printer_name: None,
+            limit: None,
+            natural_language: None,
+        }
+    }
+
+    /// Specify first-printer-name attribute: the name of the first printer to return
+    pub fn first_printer_name(mut self, name: &str) -> Self {
+        self.first_printer_name = Some(name.to_owned());
+        self
+    }
+
+    /// Specify limit attribute: the maximum number of printers to return
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    natural_language_setter!();
+
+    /// Build operation
+    pub fn build(self) -> impl IppOperation {
+        NaturalLanguageOverride::new(
+            CupsGetPrinters::with_range(self.first_printer_name.as_ref(), self.limit),
+            self.natural_language,
+        )
+    }
+}
+
+/// Builder to create Set-Printer-Attributes operation
+pub struct SetPrinterAttributesBuilder {
+    attributes: Vec<IppAttribute>,
+    natural_language: Option<String>,
+}
+
+impl SetPrinterAttributesBuilder {
+    fn new() -> SetPrinterAttributesBuilder {
+        SetPrinterAttributesBuilder {
+            attributes: Vec::new(),
+            natural_language: None,
+        }
+    }
+
+    /// Specify printer attribute to set
+    pub fn attribute(mut self, attribute: IppAttribute) -> Self {
+        self.attributes.push(attribute);
+        self
+    }
+
+    /// Delete printer attribute
+    pub fn delete_attribute(self, name: &str) -> Self {
+        self.attribute(IppAttribute::new(name, IppValue::DeleteAttribute))
+    }
+
+    /// Reset printer attribute to the administratively defined value
+    pub fn admin_define(self, name: &str) -> Self {
+        self.attribute(IppAttribute::new(name, IppValue::AdminDefine))
+    }
+
+    natural_language_setter!();
+
     /// Build operation
     pub fn build(self) -> impl IppOperation {
-        SendDocument::new(self.job_id, self.source, self.user_name.as_ref(), self.is_last)
+        let op = self
+            .attributes
+            .into_iter()
+            .fold(SetPrinterAttributes::new(), |mut op, attr| {
+                op.add_attribute(attr);
+                op
+            });
+        NaturalLanguageOverride::new(op, self.natural_language)
+    }
+}
+
+/// Builder to create Set-Job-Attributes operation
+pub struct SetJobAttributesBuilder {
+    job_id: i32,
+    attributes: Vec<IppAttribute>,
+    natural_language: Option<String>,
+}
+
+impl SetJobAttributesBuilder {
+    fn new(job_id: i32) -> SetJobAttributesBuilder {
+        SetJobAttributesBuilder {
+            job_id,
+            attributes: Vec::new(),
+            natural_language: None,
+        }
+    }
+
+    /// Specify job attribute to set
+    pub fn attribute(mut self, attribute: IppAttribute) -> Self {
+        self.attributes.push(attribute);
+        self
+    }
+
+    /// Delete job attribute, for example `job-hold-until`
+    pub fn delete_attribute(self, name: &str) -> Self {
+        self.attribute(IppAttribute::new(name, IppValue::DeleteAttribute))
+    }
+
+    /// Reset job attribute to the administratively defined value
+    pub fn admin_define(self, name: &str) -> Self {
+        self.attribute(IppAttribute::new(name, IppValue::AdminDefine))
+    }
+
+    natural_language_setter!();
+
+    /// Build operation
+    pub fn build(self) -> impl IppOperation {
+        let op = self
+            .attributes
+            .into_iter()
+            .fold(SetJobAttributes::new(self.job_id), |mut op, attr| {
+                op.add_attribute(attr);
+                op
+            });
+        NaturalLanguageOverride::new(op, self.natural_language)
+    }
+}
+
+/// Builder to create Get-Jobs operation
+pub struct GetJobsBuilder {
+    which_jobs: Option<String>,
+    my_jobs: Option<bool>,
+    limit: Option<i32>,
+    attributes: Vec<String>,
+    natural_language: Option<String>,
+}
+
+impl GetJobsBuilder {
+    fn new() -> GetJobsBuilder {
+        GetJobsBuilder {
+            which_jobs: None,
+            my_jobs: None,
+            limit: None,
+            attributes: Vec::new(),
+            natural_language: None,
+        }
+    }
+
+    /// Specify which-jobs attribute: `completed` or `not-completed`, the printer default is `not-completed`
+    pub fn which_jobs(mut self, which_jobs: &str) -> Self {
+        self.which_jobs = Some(which_jobs.to_owned());
+        self
+    }
+
+    /// Specify my-jobs attribute: return only the jobs submitted by the requesting user
+    pub fn my_jobs(mut self, my_jobs: bool) -> Self {
+        self.my_jobs = Some(my_jobs);
+        self
+    }
+
+    /// Specify limit attribute: the maximum number of jobs to return
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Specify requested-attributes: the job attributes to return for each job
+    pub fn requested_attributes(mut self, attributes: &[&str]) -> Self {
+        self.attributes.extend(attributes.iter().map(|a| (*a).to_owned()));
+        self
+    }
+
+    natural_language_setter!();
+
+    /// Build operation
+    pub fn build(self) -> impl IppOperation {
+        let mut op = GetJobs::with_attributes(self.which_jobs.as_deref(), &self.attributes);
+        if let Some(my_jobs) = self.my_jobs {
+            op.set_my_jobs(my_jobs);
+        }
+        if let Some(limit) = self.limit {
+            op.set_limit(limit);
+        }
+        NaturalLanguageOverride::new(op, self.natural_language)
+    }
+}
+
+/// Builder to create Validate-Job operation
+pub struct ValidateJobBuilder {
+    user_name: Option<String>,
+    job_title: Option<String>,
+    attributes: Vec<IppAttribute>,
+    operation_attributes: Vec<IppAttribute>,
+    natural_language: Option<String>,
+}
+
+impl ValidateJobBuilder {
+    fn new() -> ValidateJobBuilder {
+        ValidateJobBuilder {
+            user_name: None,
+            job_title: None,
+            attributes: Vec::new(),
+            operation_attributes: Vec::new(),
+            natural_language: None,
+        }
+    }
+
+    job_template_setters!();
+
+    natural_language_setter!();
+
+    /// Build operation
+    pub fn build(self) -> impl IppOperation {
+        let mut op = ValidateJob::new(self.user_name.as_ref(), self.job_title.as_ref());
+        for attr in self.operation_attributes {
+            op.add_operation_attribute(attr);
+        }
+        let op = self.attributes.into_iter().fold(op, |mut op, attr| {
+            op.add_attribute(attr);
+            op
+        });
+        NaturalLanguageOverride::new(op, self.natural_language)
+    }
+}
+
+/// Builder to create Pause-Printer operation
+pub struct PausePrinterBuilder {
+    user_name: Option<String>,
+    natural_language: Option<String>,
+}
+
+impl PausePrinterBuilder {
+    fn new() -> PausePrinterBuilder {
+        PausePrinterBuilder {
+            user_name: None,
+            natural_language: None,
+        }
+    }
+
+    /// Specify requesting-user-name attribute
+    pub fn user_name(mut self, user_name: &str) -> Self {
+        self.user_name = Some(user_name.to_owned());
+        self
+    }
+
+    natural_language_setter!();
+
+    /// Build operation
+    pub fn build(self) -> impl IppOperation {
+        NaturalLanguageOverride::new(PausePrinter::new(self.user_name.as_ref()), self.natural_language)
+    }
+}
+
+/// Builder to create Resume-Printer operation
+pub struct ResumePrinterBuilder {
+    user_name: Option<String>,
+    natural_language: Option<String>,
+}
+
+impl ResumePrinterBuilder {
+    fn new() -> ResumePrinterBuilder {
+        ResumePrinterBuilder {
+            user_name: None,
+            natural_language: None,
+        }
+    }
+
+    /// Specify requesting-user-name attribute
+    pub fn user_name(mut self, user_name: &str) -> Self {
+        self.user_name = Some(user_name.to_owned());
+        self
+    }
+
+    natural_language_setter!();
+
+    /// Build operation
+    pub fn build(self) -> impl IppOperation {
+        NaturalLanguageOverride::new(ResumePrinter::new(self.user_name.as_ref()), self.natural_language)
+    }
+}
+
+/// Builder to create Get-Job-Attributes operation
+pub struct GetJobAttributesBuilder {
+    job_id: i32,
+    attributes: Vec<String>,
+    natural_language: Option<String>,
+}
+
+impl GetJobAttributesBuilder {
+    fn new(job_id: i32) -> GetJobAttributesBuilder {
+        GetJobAttributesBuilder {
+            job_id,
+            attributes: Vec::new(),
+            natural_language: None,
+        }
+    }
+
+    /// Specify requested-attributes: the job attributes to return, for example `job-state`
+    pub fn requested_attributes(mut self, attributes: &[&str]) -> Self {
+        self.attributes.extend(attributes.iter().map(|a| (*a).to_owned()));
+        self
+    }
+
+    natural_language_setter!();
+
+    /// Build operation
+    pub fn build(self) -> impl IppOperation {
+        NaturalLanguageOverride::new(
+            GetJobAttributes::with_attributes(self.job_id, &self.attributes),
+            self.natural_language,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        io,
+        sync::{Arc, Mutex},
+        time::UNIX_EPOCH,
+    };
+
+    use futures::{Future, Stream};
+
+    use crate::{
+        ipp::{DelimiterTag, IppVersion, Operation, StatusCode},
+        parser::IppParser,
+        request::IppRequestResponse,
+        IppAttributes, JobInfo, PrinterCapabilities,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_print_job_keywords() {
+        let op = IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .sides(Sides::TwoSidedLongEdge)
+            .print_color_mode(PrintColorMode::Monochrome)
+            .media("iso_a4_210x297mm")
+            .build();
+
+        let req = op.into_ipp_request("ipp://localhost/printers/test");
+        let attrs = req.attributes().groups_of(DelimiterTag::JobAttributes)[0].attributes();
+
+        assert_eq!(
+            attrs.get(SIDES).and_then(|a| Sides::from_value(a.value())),
+            Some(Sides::TwoSidedLongEdge)
+        );
+        assert_eq!(
+            attrs
+                .get(PRINT_COLOR_MODE)
+                .and_then(|a| PrintColorMode::from_value(a.value())),
+            Some(PrintColorMode::Monochrome)
+        );
+        assert_eq!(
+            attrs.get(MEDIA).map(|a| a.value()),
+            Some(&IppValue::Keyword("iso_a4_210x297mm".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_cups_get_printers_range() {
+        let op = IppOperationBuilder::cups_get_printers()
+            .first_printer_name("printer-10")
+            .limit(10)
+            .build();
+
+        let req = op.into_ipp_request("ipp://localhost");
+        let attrs = req.attributes().groups_of(DelimiterTag::OperationAttributes)[0].attributes();
+
+        assert_eq!(
+            attrs.get(FIRST_PRINTER_NAME).map(|a| a.value()),
+            Some(&IppValue::NameWithoutLanguage("printer-10".to_owned()))
+        );
+        assert_eq!(attrs.get(LIMIT).map(|a| a.value()), Some(&IppValue::Integer(10)));
+    }
+
+    #[test]
+    fn test_printer_resolution() {
+        let attrs = job_attributes(
+            IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+                .printer_resolution(600, 600, 3)
+                .build(),
+        );
+        assert_eq!(
+            attrs[PRINTER_RESOLUTION].value(),
+            &IppValue::Resolution {
+                crossfeed: 600,
+                feed: 600,
+                units: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_jobs() {
+        let op = IppOperationBuilder::get_jobs()
+            .which_jobs("completed")
+            .my_jobs(true)
+            .limit(2)
+            .requested_attributes(&[JOB_ID, JOB_STATE])
+            .build();
+
+        let attrs = operation_attributes(op);
+        assert_eq!(
+            attrs.get(WHICH_JOBS).map(|a| a.value()),
+            Some(&IppValue::Keyword("completed".to_owned()))
+        );
+        assert_eq!(attrs.get(MY_JOBS).map(|a| a.value()), Some(&IppValue::Boolean(true)));
+        assert_eq!(attrs.get(LIMIT).map(|a| a.value()), Some(&IppValue::Integer(2)));
+        assert_eq!(
+            attrs.get(REQUESTED_ATTRIBUTES).map(|a| a.value()),
+            Some(&IppValue::ListOf(vec![
+                IppValue::Keyword(JOB_ID.to_owned()),
+                IppValue::Keyword(JOB_STATE.to_owned()),
+            ]))
+        );
+
+        // Get-Jobs response with two job groups, as returned by CUPS
+        let mut response = vec![1, 1, 0, 0, 0, 0, 0, 1, 1];
+        response.extend_from_slice(&[0x47, 0, 18]);
+        response.extend_from_slice(b"attributes-charset");
+        response.extend_from_slice(&[0, 5]);
+        response.extend_from_slice(b"utf-8");
+        for (id, state) in &[(11u8, 9u8), (12, 7)] {
+            response.push(2);
+            response.extend_from_slice(&[0x21, 0, 6]);
+            response.extend_from_slice(b"job-id");
+            response.extend_from_slice(&[0, 4, 0, 0, 0, *id]);
+            response.extend_from_slice(&[0x23, 0, 9]);
+            response.extend_from_slice(b"job-state");
+            response.extend_from_slice(&[0, 4, 0, 0, 0, *state]);
+        }
+        response.push(3);
+
+        let result = IppParser::new(&mut io::Cursor::new(response)).parse().unwrap();
+        let jobs = result.attributes.groups_of(DelimiterTag::JobAttributes);
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].attributes()[JOB_ID].value(), &IppValue::Integer(11));
+        assert_eq!(jobs[0].attributes()[JOB_STATE].value(), &IppValue::Enum(9));
+        assert_eq!(jobs[1].attributes()[JOB_ID].value(), &IppValue::Integer(12));
+        assert_eq!(jobs[1].attributes()[JOB_STATE].value(), &IppValue::Enum(7));
+    }
+
+    #[test]
+    fn test_validate_job() {
+        let op = IppOperationBuilder::validate_job()
+            .user_name("user")
+            .job_title("report")
+            .media("iso_a4_210x297mm")
+            .attribute_fidelity(true)
+            .operation_attribute(IppAttribute::new(
+                DOCUMENT_FORMAT,
+                IppValue::MimeMediaType("application/pdf".to_owned()),
+            ))
+            .build();
+
+        let mut buf = Vec::new();
+        op.into_ipp_request("ipp://localhost/printers/test")
+            .write(&mut buf)
+            .unwrap();
+        let result = IppParser::new(&mut io::Cursor::new(buf)).parse().unwrap();
+        assert_eq!(result.header.operation_status, Operation::ValidateJob as u16);
+        assert!(result.payload.is_none());
+
+        let attrs = result.attributes.groups_of(DelimiterTag::OperationAttributes)[0].attributes();
+        assert_eq!(
+            attrs[REQUESTING_USER_NAME].value(),
+            &IppValue::NameWithoutLanguage("user".to_owned())
+        );
+        assert_eq!(
+            attrs[JOB_NAME].value(),
+            &IppValue::NameWithoutLanguage("report".to_owned())
+        );
+        assert_eq!(attrs[IPP_ATTRIBUTE_FIDELITY].value(), &IppValue::Boolean(true));
+        assert_eq!(
+            attrs[DOCUMENT_FORMAT].value(),
+            &IppValue::MimeMediaType("application/pdf".to_owned())
+        );
+
+        let attrs = result.attributes.groups_of(DelimiterTag::JobAttributes)[0].attributes();
+        assert_eq!(attrs[MEDIA].value(), &IppValue::Keyword("iso_a4_210x297mm".to_owned()));
+    }
+
+    #[test]
+    fn test_print_uri() {
+        let op = IppOperationBuilder::print_uri("https://example.com/report.pdf")
+            .user_name("user")
+            .job_title("report")
+            .document_format("application/pdf")
+            .sides(Sides::TwoSidedLongEdge)
+            .build();
+
+        let mut buf = Vec::new();
+        op.into_ipp_request("ipp://localhost/printers/test")
+            .write(&mut buf)
+            .unwrap();
+
+        let mut expected = vec![0x45, 0, 12];
+        expected.extend_from_slice(b"document-uri");
+        expected.extend_from_slice(&[0, 30]);
+        expected.extend_from_slice(b"https://example.com/report.pdf");
+        assert!(buf.windows(expected.len()).any(|w| w == &expected[..]));
+
+        let result = IppParser::new(&mut io::Cursor::new(buf)).parse().unwrap();
+        assert_eq!(result.header.operation_status, Operation::PrintUri as u16);
+        assert!(result.payload.is_none());
+
+        let attrs = result.attributes.groups_of(DelimiterTag::OperationAttributes)[0].attributes();
+        assert_eq!(
+            attrs[DOCUMENT_URI].value(),
+            &IppValue::Uri("https://example.com/report.pdf".to_owned())
+        );
+        assert_eq!(
+            attrs[JOB_NAME].value(),
+            &IppValue::NameWithoutLanguage("report".to_owned())
+        );
+        assert_eq!(
+            attrs[DOCUMENT_FORMAT].value(),
+            &IppValue::MimeMediaType("application/pdf".to_owned())
+        );
+
+        let attrs = result.attributes.groups_of(DelimiterTag::JobAttributes)[0].attributes();
+        assert_eq!(
+            attrs[SIDES].value(),
+            &IppValue::Keyword("two-sided-long-edge".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_pause_resume_printer() {
+        fn parse(op: impl IppOperation) -> crate::parser::IppParseResult {
+            let mut buf = Vec::new();
+            op.into_ipp_request("ipp://localhost/printers/test")
+                .write(&mut buf)
+                .unwrap();
+            IppParser::new(&mut io::Cursor::new(buf)).parse().unwrap()
+        }
+
+        let result = parse(IppOperationBuilder::pause_printer().user_name("admin").build());
+        assert_eq!(result.header.operation_status, Operation::PausePrinter as u16);
+        let attrs = result.attributes.groups_of(DelimiterTag::OperationAttributes)[0].attributes();
+        assert_eq!(
+            attrs[PRINTER_URI].value(),
+            &IppValue::Uri("ipp://localhost/printers/test".to_owned())
+        );
+        assert_eq!(
+            attrs[REQUESTING_USER_NAME].value(),
+            &IppValue::NameWithoutLanguage("admin".to_owned())
+        );
+
+        let result = parse(IppOperationBuilder::resume_printer().build());
+        assert_eq!(result.header.operation_status, Operation::ResumePrinter as u16);
+        let attrs = result.attributes.groups_of(DelimiterTag::OperationAttributes)[0].attributes();
+        assert_eq!(
+            attrs[PRINTER_URI].value(),
+            &IppValue::Uri("ipp://localhost/printers/test".to_owned())
+        );
+        assert!(!attrs.contains_key(REQUESTING_USER_NAME));
+    }
+
+    #[test]
+    fn test_get_job_attributes() {
+        let op = IppOperationBuilder::get_job_attributes(42)
+            .requested_attributes(&[JOB_STATE, JOB_STATE_REASONS])
+            .build();
+
+        let mut buf = Vec::new();
+        op.into_ipp_request("ipp://localhost/printers/test")
+            .write(&mut buf)
+            .unwrap();
+        let result = IppParser::new(&mut io::Cursor::new(buf)).parse().unwrap();
+        assert_eq!(result.header.operation_status, Operation::GetJobAttributes as u16);
+
+        let attrs = result.attributes.groups_of(DelimiterTag::OperationAttributes)[0].attributes();
+        assert_eq!(attrs[JOB_ID].value(), &IppValue::Integer(42));
+        assert_eq!(
+            attrs[REQUESTED_ATTRIBUTES].value(),
+            &IppValue::ListOf(vec![
+                IppValue::Keyword(JOB_STATE.to_owned()),
+                IppValue::Keyword(JOB_STATE_REASONS.to_owned()),
+            ])
+        );
+        assert!(result.attributes.job_attributes().is_none());
+
+        let mut response = IppRequestResponse::new_response(IppVersion::Ipp11, StatusCode::SuccessfulOK, 1);
+        response.attributes_mut().add(
+            DelimiterTag::JobAttributes,
+            IppAttribute::new(JOB_ID, IppValue::Integer(42)),
+        );
+        let job = response.attributes().job_attributes().unwrap();
+        assert_eq!(job.attributes()[JOB_ID].value(), &IppValue::Integer(42));
+    }
+
+    #[test]
+    fn test_document_format_details() {
+        let details = DocumentFormatDetailsBuilder::new()
+            .document_format("application/pdf")
+            .document_format_version("1.7")
+            .source_application_name("ipp.rs")
+            .build();
+
+        let op = IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .document_format_details(details.clone())
+            .build();
+
+        let mut buf = Vec::new();
+        op.into_ipp_request("ipp://localhost/printers/test")
+            .write(&mut buf)
+            .unwrap();
+
+        let result = IppParser::new(&mut io::Cursor::new(buf)).parse().unwrap();
+        let attrs = result.attributes.groups_of(DelimiterTag::OperationAttributes)[0].attributes();
+        let value = attrs.get(DOCUMENT_FORMAT_DETAILS).unwrap().value();
+
+        assert_eq!(value, &details);
+        assert_eq!(
+            value.member(DOCUMENT_FORMAT),
+            Some(IppValue::MimeMediaType("application/pdf".to_owned()))
+        );
+        assert_eq!(
+            value.member(DOCUMENT_SOURCE_APPLICATION_NAME),
+            Some(IppValue::NameWithoutLanguage("ipp.rs".to_owned()))
+        );
+        assert_eq!(value.member(DOCUMENT_SOURCE_OS_NAME), None);
+    }
+
+    #[test]
+    fn test_finishings_col() {
+        let finishings = crate::FinishingsColBuilder::new()
+            .finishing_template("staple")
+            .stitching("left", &[5000, 20000], Some(1000))
+            .punching("left", &[], None)
+            .build();
+
+        let builder = IppOperationBuilder::print_job(io::Cursor::new(Vec::new())).finishings_col(finishings.clone());
+
+        let caps = caps(&[(
+            "finishings-col-supported",
+            IppValue::ListOf(vec![
+                IppValue::Keyword(FINISHING_TEMPLATE.to_owned()),
+                IppValue::Keyword(STITCHING.to_owned()),
+            ]),
+        )]);
+        // punching member is not listed in finishings-col-supported
+        assert_eq!(
+            caps.validate_job_attributes(&builder.attributes),
+            Err(vec![FINISHINGS_COL.to_owned()])
+        );
+
+        let mut buf = Vec::new();
+        builder
+            .build()
+            .into_ipp_request("ipp://localhost/printers/test")
+            .write(&mut buf)
+            .unwrap();
+
+        let result = IppParser::new(&mut io::Cursor::new(buf)).parse().unwrap();
+        let attrs = result.attributes.groups_of(DelimiterTag::JobAttributes)[0].attributes();
+        let value = attrs.get(FINISHINGS_COL).unwrap().value();
+
+        assert_eq!(FinishingsCol::from_value(value), Some(finishings));
+        let stitching = value.member(STITCHING).unwrap();
+        assert_eq!(
+            stitching.member(STITCHING_REFERENCE_EDGE),
+            Some(IppValue::Keyword("left".to_owned()))
+        );
+        assert_eq!(
+            stitching.member(STITCHING_LOCATIONS),
+            Some(IppValue::ListOf(vec![
+                IppValue::Integer(5000),
+                IppValue::Integer(20000)
+            ]))
+        );
+        assert_eq!(stitching.member(STITCHING_OFFSET), Some(IppValue::Integer(1000)));
+        assert_eq!(value.member(PUNCHING).unwrap().member(PUNCHING_LOCATIONS), None);
+    }
+
+    #[test]
+    fn test_mandatory_attributes() {
+        let op = IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .sides(Sides::TwoSidedLongEdge)
+            .media("iso_a4_210x297mm")
+            .mandatory_attributes(&[SIDES, MEDIA])
+            .attribute_fidelity(true)
+            .build();
+
+        let mut buf = Vec::new();
+        op.into_ipp_request("ipp://localhost/printers/test")
+            .write(&mut buf)
+            .unwrap();
+
+        let result = IppParser::new(&mut io::Cursor::new(buf)).parse().unwrap();
+        let attrs = result.attributes.groups_of(DelimiterTag::OperationAttributes)[0].attributes();
+
+        assert_eq!(
+            attrs.get(JOB_MANDATORY_ATTRIBUTES).unwrap().value(),
+            &IppValue::ListOf(vec![
+                IppValue::Keyword(SIDES.to_owned()),
+                IppValue::Keyword(MEDIA.to_owned())
+            ])
+        );
+        assert_eq!(
+            attrs.get(IPP_ATTRIBUTE_FIDELITY).unwrap().value(),
+            &IppValue::Boolean(true)
+        );
+    }
+
+    #[test]
+    fn test_set_job_attributes() {
+        let op = IppOperationBuilder::set_job_attributes(10)
+            .delete_attribute("job-hold-until")
+            .admin_define("job-priority")
+            .build();
+
+        let mut buf = Vec::new();
+        op.into_ipp_request("ipp://localhost/printers/test")
+            .write(&mut buf)
+            .unwrap();
+
+        let result = IppParser::new(&mut io::Cursor::new(buf)).parse().unwrap();
+        let attrs = result.attributes.groups_of(DelimiterTag::OperationAttributes)[0].attributes();
+        assert_eq!(attrs.get(JOB_ID).unwrap().value(), &IppValue::Integer(10));
+
+        let attrs = result.attributes.groups_of(DelimiterTag::JobAttributes)[0].attributes();
+        assert_eq!(attrs.get("job-hold-until").unwrap().value(), &IppValue::DeleteAttribute);
+        assert_eq!(attrs.get("job-priority").unwrap().value(), &IppValue::AdminDefine);
+    }
+
+    fn operation_attributes(op: impl IppOperation) -> HashMap<String, IppAttribute> {
+        let mut buf = Vec::new();
+        op.into_ipp_request("ipp://localhost/printers/test")
+            .write(&mut buf)
+            .unwrap();
+
+        let result = IppParser::new(&mut io::Cursor::new(buf)).parse().unwrap();
+        result.attributes.groups_of(DelimiterTag::OperationAttributes)[0]
+            .attributes()
+            .clone()
+    }
+
+    fn job_attributes(op: impl IppOperation) -> HashMap<String, IppAttribute> {
+        let mut buf = Vec::new();
+        op.into_ipp_request("ipp://localhost/printers/test")
+            .write(&mut buf)
+            .unwrap();
+
+        let result = IppParser::new(&mut io::Cursor::new(buf)).parse().unwrap();
+        result.attributes.groups_of(DelimiterTag::JobAttributes)[0]
+            .attributes()
+            .clone()
+    }
+
+    fn caps(attributes: &[(&str, IppValue)]) -> PrinterCapabilities {
+        let mut attrs = IppAttributes::new();
+        for (name, value) in attributes {
+            attrs.add(DelimiterTag::PrinterAttributes, IppAttribute::new(name, value.clone()));
+        }
+        PrinterCapabilities::from_attributes(&attrs)
+    }
+
+    #[test]
+    fn test_media_col() {
+        let media = crate::MediaColBuilder::new()
+            .size(21000, 29700)
+            .media_type("stationery")
+            .top_margin(300)
+            .build();
+        let builder = IppOperationBuilder::print_job(io::Cursor::new(Vec::new())).media_col(media.clone());
+
+        let attrs = job_attributes(builder.build());
+        let value = attrs.get(MEDIA_COL).unwrap().value();
+        assert_eq!(value.member(MEDIA_TOP_MARGIN), Some(IppValue::Integer(300)));
+        assert_eq!(value.member(MEDIA_SOURCE), None);
+        assert_eq!(MediaCol::from_value(value), Some(media));
+    }
+
+    #[test]
+    fn test_job_sheets() {
+        let builder = IppOperationBuilder::print_job(io::Cursor::new(Vec::new())).job_sheets("standard");
+
+        let caps = caps(&[(
+            "job-sheets-supported",
+            IppValue::ListOf(vec![IppValue::Keyword("none".to_owned())]),
+        )]);
+        assert_eq!(
+            caps.validate_job_attributes(&builder.attributes),
+            Err(vec![JOB_SHEETS.to_owned()])
+        );
+
+        let attrs = job_attributes(builder.build());
+        assert_eq!(
+            attrs.get(JOB_SHEETS).unwrap().value(),
+            &IppValue::Keyword("standard".to_owned())
+        );
+
+        let op = IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .job_sheets_start_end("confidential", "none")
+            .build();
+        let attrs = job_attributes(op);
+        assert_eq!(
+            attrs.get(JOB_SHEETS).unwrap().value(),
+            &IppValue::ListOf(vec![
+                IppValue::Keyword("confidential".to_owned()),
+                IppValue::Keyword("none".to_owned())
+            ])
+        );
+    }
+
+    #[test]
+    fn test_layout_keywords() {
+        let builder = IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .presentation_direction("toright-tobottom")
+            .imposition_template("booklet");
+
+        let caps = caps(&[(
+            "imposition-template-supported",
+            IppValue::ListOf(vec![IppValue::Keyword("none".to_owned())]),
+        )]);
+        // presentation-direction-number-up-supported is not reported and is not checked
+        assert_eq!(
+            caps.validate_job_attributes(&builder.attributes),
+            Err(vec![IMPOSITION_TEMPLATE.to_owned()])
+        );
+
+        let attrs = job_attributes(builder.build());
+        assert_eq!(
+            attrs.get(PRESENTATION_DIRECTION_NUMBER_UP).unwrap().value(),
+            &IppValue::Keyword("toright-tobottom".to_owned())
+        );
+        assert_eq!(
+            attrs.get(IMPOSITION_TEMPLATE).unwrap().value(),
+            &IppValue::Keyword("booklet".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_job_sheets_col() {
+        let op = IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .job_sheets_col("standard", Some("iso_a4_210x297mm"))
+            .build();
+
+        let attrs = job_attributes(op);
+        let value = attrs.get(JOB_SHEETS_COL).unwrap().value();
+        assert_eq!(value.member(JOB_SHEETS), Some(IppValue::Keyword("standard".to_owned())));
+        assert_eq!(
+            value.member(MEDIA),
+            Some(IppValue::Keyword("iso_a4_210x297mm".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_parse_page_ranges() {
+        assert_eq!(parse_page_ranges("1-3,5,8-10").unwrap(), vec![(1, 3), (5, 5), (8, 10)]);
+        assert_eq!(parse_page_ranges(" 2 - 4 , 7").unwrap(), vec![(2, 4), (7, 7)]);
+        assert_eq!(parse_page_ranges("3-3").unwrap(), vec![(3, 3)]);
+
+        for ranges in &["3-1", "abc", "", "0", "1-", "-2", "1,,2", "1-2-3", "-1", "5-x"] {
+            assert!(parse_page_ranges(ranges).is_err(), "{} should be rejected", ranges);
+        }
+    }
+
+    #[test]
+    fn test_page_ranges_str() {
+        let op = IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .page_ranges_str("1-3,5")
+            .unwrap()
+            .build();
+
+        let attrs = job_attributes(op);
+        assert_eq!(
+            attrs.get(PAGE_RANGES).unwrap().value(),
+            &IppValue::ListOf(vec![
+                IppValue::RangeOfInteger { min: 1, max: 3 },
+                IppValue::RangeOfInteger { min: 5, max: 5 }
+            ])
+        );
+
+        match IppOperationBuilder::print_job(io::Cursor::new(Vec::new())).page_ranges_str("3-1") {
+            Err(ParseError::InvalidValue(ref s)) => assert_eq!(s, "3-1"),
+            _ => panic!("Invalid page range accepted"),
+        }
+    }
+
+    #[test]
+    fn test_job_error_sheet() {
+        let op = IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .job_error_sheet("standard", Some("on-error"))
+            .build();
+
+        let attrs = job_attributes(op);
+        let value = attrs.get(JOB_ERROR_SHEET).unwrap().value();
+        assert_eq!(
+            value.member(JOB_ERROR_SHEET_TYPE),
+            Some(IppValue::Keyword("standard".to_owned()))
+        );
+        assert_eq!(
+            value.member(JOB_ERROR_SHEET_WHEN),
+            Some(IppValue::Keyword("on-error".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_cancel_after_retain_until() {
+        let builder = IppOperationBuilder::create_job()
+            .cancel_after(Duration::from_millis(90_500))
+            .retain_until("end-of-day");
+
+        let caps = caps(&[
+            (
+                "job-cancel-after-supported",
+                IppValue::RangeOfInteger { min: 1, max: 60 },
+            ),
+            (
+                "job-retain-until-supported",
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("none".to_owned()),
+                    IppValue::Keyword("end-of-day".to_owned()),
+                ]),
+            ),
+        ]);
+        assert_eq!(
+            caps.validate_job_attributes(&builder.attributes),
+            Err(vec![JOB_CANCEL_AFTER.to_owned()])
+        );
+
+        let attrs = job_attributes(builder.build());
+        assert_eq!(attrs.get(JOB_CANCEL_AFTER).unwrap().value(), &IppValue::Integer(90));
+        assert_eq!(
+            attrs.get(JOB_RETAIN_UNTIL).unwrap().value(),
+            &IppValue::Keyword("end-of-day".to_owned())
+        );
+
+        let op = IppOperationBuilder::create_job()
+            .cancel_after(Duration::from_secs(u64::MAX))
+            .build();
+        let attrs = job_attributes(op);
+        assert_eq!(
+            attrs.get(JOB_CANCEL_AFTER).unwrap().value(),
+            &IppValue::Integer(i32::MAX)
+        );
+    }
+
+    #[test]
+    fn test_delay_output_until() {
+        let builder = IppOperationBuilder::create_job().delay_output_until("night");
+
+        let caps = caps(&[
+            (
+                "job-delay-output-until-supported",
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("no-delay-output".to_owned()),
+                    IppValue::Keyword("weekend".to_owned()),
+                ]),
+            ),
+            (
+                "job-delay-output-until-time-supported",
+                IppValue::RangeOfInteger { min: 0, max: 86400 },
+            ),
+        ]);
+        assert_eq!(
+            caps.validate_job_attributes(&builder.attributes),
+            Err(vec![JOB_DELAY_OUTPUT_UNTIL.to_owned()])
+        );
+
+        let attrs = job_attributes(builder.build());
+        assert_eq!(
+            attrs.get(JOB_DELAY_OUTPUT_UNTIL).unwrap().value(),
+            &IppValue::Keyword("night".to_owned())
+        );
+
+        let time = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+        let attrs = job_attributes(IppOperationBuilder::create_job().delay_output_until_time(time).build());
+        let value = attrs.get(JOB_DELAY_OUTPUT_UNTIL_TIME).unwrap().value();
+        assert!(matches!(
+            value,
+            IppValue::DateTime {
+                year: 2020,
+                month: 9,
+                day: 13,
+                ..
+            }
+        ));
+        assert_eq!(value.to_system_time(), Some(time));
+
+        let in_hour = SystemTime::now() + Duration::from_secs(3600);
+        let in_week = SystemTime::now() + Duration::from_secs(7 * 86400);
+        let builder = IppOperationBuilder::create_job().delay_output_until_time(in_hour);
+        assert!(caps.validate_job_attributes(&builder.attributes).is_ok());
+        let builder = IppOperationBuilder::create_job().delay_output_until_time(in_week);
+        assert_eq!(
+            caps.validate_job_attributes(&builder.attributes),
+            Err(vec![JOB_DELAY_OUTPUT_UNTIL_TIME.to_owned()])
+        );
+    }
+
+    #[test]
+    fn test_separator_sheets() {
+        let builder = IppOperationBuilder::create_job().separator_sheets("slip-sheets", Some("na_letter_8.5x11in"));
+
+        let caps = caps(&[
+            (
+                "separator-sheets-supported",
+                IppValue::ListOf(vec![IppValue::Keyword(SEPARATOR_SHEETS_TYPE.to_owned())]),
+            ),
+            (
+                "separator-sheets-type-supported",
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("none".to_owned()),
+                    IppValue::Keyword("slip-sheets".to_owned()),
+                ]),
+            ),
+        ]);
+        // media member is not listed in separator-sheets-supported
+        assert_eq!(
+            caps.validate_job_attributes(&builder.attributes),
+            Err(vec![SEPARATOR_SHEETS.to_owned()])
+        );
+        assert!(caps
+            .validate_job_attributes(&[separator_sheets("slip-sheets", None)])
+            .is_ok());
+        assert!(caps
+            .validate_job_attributes(&[separator_sheets("both-sheets", None)])
+            .is_err());
+
+        let attrs = job_attributes(builder.build());
+        let value = attrs.get(SEPARATOR_SHEETS).unwrap().value();
+        assert_eq!(
+            value.member(SEPARATOR_SHEETS_TYPE),
+            Some(IppValue::Keyword("slip-sheets".to_owned()))
+        );
+        assert_eq!(
+            value.member(MEDIA),
+            Some(IppValue::Keyword("na_letter_8.5x11in".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_requested_attributes() {
+        let op = IppOperationBuilder::get_printer_attributes()
+            .request(RequestedAttribute::PrinterState)
+            .request(RequestedAttribute::JobTemplate)
+            .attribute("printer-kind")
+            .build();
+
+        let attrs = operation_attributes(op);
+        assert_eq!(
+            attrs.get(REQUESTED_ATTRIBUTES).unwrap().value(),
+            &IppValue::ListOf(vec![
+                IppValue::Keyword(PRINTER_STATE.to_owned()),
+                IppValue::Keyword("job-template".to_owned()),
+                IppValue::Keyword("printer-kind".to_owned()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_natural_language() {
+        let op = IppOperationBuilder::get_printer_attributes()
+            .natural_language("de")
+            .build();
+        assert_eq!(op.natural_language(), Some("de"));
+
+        let mut buf = Vec::new();
+        op.into_ipp_request("ipp://localhost/printers/test")
+            .write(&mut buf)
+            .unwrap();
+
+        let result = IppParser::new(&mut io::Cursor::new(buf)).parse().unwrap();
+        let attrs = result.attributes.groups_of(DelimiterTag::OperationAttributes)[0].attributes();
+        assert_eq!(
+            attrs.get(ATTRIBUTES_NATURAL_LANGUAGE).unwrap().value(),
+            &IppValue::NaturalLanguage("de".to_owned())
+        );
+
+        let op = IppOperationBuilder::create_job().build();
+        assert_eq!(op.natural_language(), None);
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_tee_to() {
+        let archive = SharedBuffer::default();
+        let document = vec![b'x'; 100_000];
+
+        let op = IppOperationBuilder::print_job(io::Cursor::new(document.clone()))
+            .job_title("audit")
+            .tee_to(archive.clone())
+            .build();
+
+        let sent = op
+            .into_ipp_request("ipp://localhost/printers/test")
+            .into_stream()
+            .concat2()
+            .wait()
+            .unwrap();
+
+        assert!(sent.ends_with(&document));
+        assert_eq!(&sent[..], &archive.0.lock().unwrap()[..]);
+    }
+
+    #[test]
+    fn test_on_progress() {
+        let document = vec![b'x'; 1024 * 1024];
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let values = progress.clone();
+
+        let op = IppOperationBuilder::print_job(io::Cursor::new(document.clone()))
+            .on_progress(move |sent| values.lock().unwrap().push(sent))
+            .build();
+
+        let sent = op
+            .into_ipp_request("ipp://localhost/printers/test")
+            .into_stream()
+            .concat2()
+            .wait()
+            .unwrap();
+        assert!(sent.ends_with(&document));
+
+        let progress = progress.lock().unwrap();
+        assert!(progress.len() > 1);
+        assert!(progress.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(progress.last(), Some(&(document.len() as u64)));
+    }
+
+    #[test]
+    fn test_detect_format() {
+        let documents: &[(&[u8], &str)] = &[
+            (b"%PDF-1.7\n%\xe2\xe3", "application/pdf"),
+            (b"\xff\xd8\xff\xe0\x00\x10JFIF", "image/jpeg"),
+            (b"%!PS-Adobe-3.0\n", "application/postscript"),
+            (b"\x00\x01\x02\x03\x04", "application/octet-stream"),
+        ];
+
+        for (document, format) in documents {
+            let op = IppOperationBuilder::print_job(io::Cursor::new(document.to_vec()))
+                .detect_format(true)
+                .build();
+
+            let sent = op
+                .into_ipp_request("ipp://localhost/printers/test")
+                .into_stream()
+                .concat2()
+                .wait()
+                .unwrap();
+            assert!(sent.ends_with(document));
+
+            let result = IppParser::new(&mut io::Cursor::new(sent)).parse().unwrap();
+            let attrs = result.attributes.groups_of(DelimiterTag::OperationAttributes)[0].attributes();
+            assert_eq!(
+                attrs[DOCUMENT_FORMAT].value(),
+                &IppValue::MimeMediaType((*format).to_owned())
+            );
+        }
+
+        let attrs = operation_attributes(IppOperationBuilder::print_job(io::Cursor::new(b"%PDF".to_vec())).build());
+        assert!(!attrs.contains_key(DOCUMENT_FORMAT));
+    }
+
+    #[test]
+    fn test_output_device_uuid() {
+        let op = IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .output_device_uuid("4509A320-00A0-008F-00B6-002507510ECA")
+            .unwrap()
+            .build();
+
+        let attrs = job_attributes(op);
+        assert_eq!(
+            attrs.get(OUTPUT_DEVICE_UUID).unwrap().value(),
+            &IppValue::Uri("urn:uuid:4509a320-00a0-008f-00b6-002507510eca".to_owned())
+        );
+
+        assert!(parse_uuid_urn("urn:uuid:4509a320-00a0-008f-00b6-002507510eca").is_ok());
+        for uuid in &[
+            "",
+            "urn:uuid:",
+            "4509a320-00a0-008f-00b6",
+            "4509a32000a0008f00b6002507510eca",
+            "4509a320-00a0-008f-00b6-002507510ecx",
+            "4509a320-00a0-008f-00b6-002507510eca-0",
+        ] {
+            assert!(parse_uuid_urn(uuid).is_err(), "{} should be rejected", uuid);
+        }
+    }
+
+    #[test]
+    fn test_job_uuid() {
+        let op = IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .job_uuid("urn:uuid:4509A320-00A0-008F-00B6-002507510ECA")
+            .unwrap()
+            .build();
+
+        let mut buf = Vec::new();
+        op.into_ipp_request("ipp://localhost/printers/test")
+            .write(&mut buf)
+            .unwrap();
+        let result = IppParser::new(&mut io::Cursor::new(buf)).parse().unwrap();
+        let job = JobInfo::from_group(result.attributes.groups_of(DelimiterTag::JobAttributes)[0]);
+
+        assert_eq!(
+            job.attributes().get(JOB_UUID).unwrap().value(),
+            &IppValue::Uri("urn:uuid:4509a320-00a0-008f-00b6-002507510eca".to_owned())
+        );
+        assert_eq!(job.uuid(), Some("4509a320-00a0-008f-00b6-002507510eca"));
+
+        assert!(IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .job_uuid("not-a-uuid")
+            .is_err());
+    }
+
+    #[test]
+    fn test_document_charset() {
+        let caps = caps(&[(
+            "document-charset-supported",
+            IppValue::ListOf(vec![IppValue::Charset("us-ascii".to_owned())]),
+        )]);
+        assert!(!caps.is_supported(DOCUMENT_CHARSET, &IppValue::Charset("utf-8".to_owned())));
+
+        let op = IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .document_charset("UTF-8")
+            .build();
+        let attrs = operation_attributes(op);
+        assert_eq!(
+            attrs.get(DOCUMENT_CHARSET).unwrap().value(),
+            &IppValue::Charset("utf-8".to_owned())
+        );
+
+        let op = IppOperationBuilder::send_document(1, io::Cursor::new(Vec::new()))
+            .document_charset("iso-8859-1")
+            .build();
+        let attrs = operation_attributes(op);
+        assert_eq!(
+            attrs.get(DOCUMENT_CHARSET).unwrap().value(),
+            &IppValue::Charset("iso-8859-1".to_owned())
+        );
+        assert_eq!(attrs.get(JOB_ID).unwrap().value(), &IppValue::Integer(1));
     }
 }
@@ -0,0 +1,197 @@
+//!
+//! Finishings collection helpers
+//!
+use crate::{attribute::*, IppValue};
+
+/// Position of stitching or punching, all lengths are in hundredths of millimeters
+#[derive(Clone, Debug, PartialEq)]
+pub struct FinishingPosition {
+    /// Reference edge member, for example `top` or `left`
+    pub reference_edge: String,
+    /// Locations member: positions along the reference edge
+    pub locations: Vec<i32>,
+    /// Offset member: distance from the reference edge
+    pub offset: Option<i32>,
+}
+
+impl FinishingPosition {
+    fn from_value(value: &IppValue, edge: &str, locations: &str, offset: &str) -> Option<FinishingPosition> {
+        Some(FinishingPosition {
+            reference_edge: value.member(edge)?.as_str()?.to_owned(),
+            locations: value
+                .member(locations)
+                .map(|v| v.into_iter().filter_map(|l| l.as_integer().cloned()).collect())
+                .unwrap_or_default(),
+            offset: value.member(offset).and_then(|v| v.as_integer().cloned()),
+        })
+    }
+
+    fn to_value(&self, edge: &str, locations: &str, offset: &str) -> IppValue {
+        let mut members = vec![(edge, IppValue::Keyword(self.reference_edge.clone()))];
+        if !self.locations.is_empty() {
+            members.push((
+                locations,
+                IppValue::ListOf(self.locations.iter().map(|l| IppValue::Integer(*l)).collect()),
+            ));
+        }
+        if let Some(offset_value) = self.offset {
+            members.push((offset, IppValue::Integer(offset_value)));
+        }
+        IppValue::collection(members)
+    }
+}
+
+/// Finishings collection (finishings-col) with stitching and punching details
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FinishingsCol {
+    /// finishing-template member, for example `staple-top-left` or `booklet-maker`
+    pub finishing_template: Option<String>,
+    /// stitching member
+    pub stitching: Option<FinishingPosition>,
+    /// punching member
+    pub punching: Option<FinishingPosition>,
+}
+
+impl FinishingsCol {
+    /// Parse finishings collection from the collection value. Returns None for other value types.
+    pub fn from_value(value: &IppValue) -> Option<FinishingsCol> {
+        value.as_collection()?;
+
+        Some(FinishingsCol {
+            finishing_template: value
+                .member(FINISHING_TEMPLATE)
+                .and_then(|v| v.as_str().map(ToOwned::to_owned)),
+            stitching: value.member(STITCHING).and_then(|v| {
+                FinishingPosition::from_value(&v, STITCHING_REFERENCE_EDGE, STITCHING_LOCATIONS, STITCHING_OFFSET)
+            }),
+            punching: value.member(PUNCHING).and_then(|v| {
+                FinishingPosition::from_value(&v, PUNCHING_REFERENCE_EDGE, PUNCHING_LOCATIONS, PUNCHING_OFFSET)
+            }),
+        })
+    }
+
+    /// Convert finishings collection into the finishings-col collection value. Unset members are omitted.
+    pub fn to_value(&self) -> IppValue {
+        let mut members = Vec::new();
+        if let Some(ref template) = self.finishing_template {
+            members.push((FINISHING_TEMPLATE, IppValue::Keyword(template.clone())));
+        }
+        if let Some(ref stitching) = self.stitching {
+            members.push((
+                STITCHING,
+                stitching.to_value(STITCHING_REFERENCE_EDGE, STITCHING_LOCATIONS, STITCHING_OFFSET),
+            ));
+        }
+        if let Some(ref punching) = self.punching {
+            members.push((
+                PUNCHING,
+                punching.to_value(PUNCHING_REFERENCE_EDGE, PUNCHING_LOCATIONS, PUNCHING_OFFSET),
+            ));
+        }
+        IppValue::collection(members)
+    }
+}
+
+/// Builder to create finishings-col collection
+#[derive(Default)]
+pub struct FinishingsColBuilder {
+    finishings: FinishingsCol,
+}
+
+impl FinishingsColBuilder {
+    /// Create finishings-col builder
+    pub fn new() -> FinishingsColBuilder {
+        FinishingsColBuilder::default()
+    }
+
+    /// Specify finishing-template member, for example `staple-top-left` or `booklet-maker`
+    pub fn finishing_template(mut self, template: &str) -> Self {
+        self.finishings.finishing_template = Some(template.to_owned());
+        self
+    }
+
+    /// Specify stitching member collection
+    ///
+    /// * `reference_edge` - stitching-reference-edge, for example `top` or `left`<br/>
+    /// * `locations` - stitching-locations along the reference edge in hundredths of millimeters<br/>
+    /// * `offset` - optional stitching-offset from the reference edge in hundredths of millimeters<br/>
+    pub fn stitching(mut self, reference_edge: &str, locations: &[i32], offset: Option<i32>) -> Self {
+        self.finishings.stitching = Some(FinishingPosition {
+            reference_edge: reference_edge.to_owned(),
+            locations: locations.to_vec(),
+            offset,
+        });
+        self
+    }
+
+    /// Specify punching member collection
+    ///
+    /// * `reference_edge` - punching-reference-edge, for example `left`<br/>
+    /// * `locations` - punching-locations along the reference edge in hundredths of millimeters<br/>
+    /// * `offset` - optional punching-offset from the reference edge in hundredths of millimeters<br/>
+    pub fn punching(mut self, reference_edge: &str, locations: &[i32], offset: Option<i32>) -> Self {
+        self.finishings.punching = Some(FinishingPosition {
+            reference_edge: reference_edge.to_owned(),
+            locations: locations.to_vec(),
+            offset,
+        });
+        self
+    }
+
+    /// Build finishings collection
+    pub fn build(self) -> FinishingsCol {
+        self.finishings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finishings_col_from_value() {
+        let value = IppValue::collection(vec![
+            (FINISHING_TEMPLATE, IppValue::Keyword("staple".to_owned())),
+            (
+                STITCHING,
+                IppValue::collection(vec![
+                    (STITCHING_REFERENCE_EDGE, IppValue::Keyword("top".to_owned())),
+                    (STITCHING_LOCATIONS, IppValue::Integer(1000)),
+                ]),
+            ),
+        ]);
+
+        let finishings = FinishingsCol::from_value(&value).unwrap();
+        assert_eq!(finishings.finishing_template, Some("staple".to_owned()));
+        assert_eq!(
+            finishings.stitching,
+            Some(FinishingPosition {
+                reference_edge: "top".to_owned(),
+                locations: vec![1000],
+                offset: None,
+            })
+        );
+        assert_eq!(finishings.punching, None);
+
+        assert_eq!(FinishingsCol::from_value(&IppValue::Keyword("staple".to_owned())), None);
+    }
+
+    #[test]
+    fn test_finishings_col_to_value() {
+        let finishings = FinishingsColBuilder::new()
+            .stitching("left", &[5000, 20000], Some(1000))
+            .punching("left", &[], None)
+            .build();
+        let value = finishings.to_value();
+
+        assert_eq!(value.member(FINISHING_TEMPLATE), None);
+        assert_eq!(
+            value.member(PUNCHING),
+            Some(IppValue::collection(vec![(
+                PUNCHING_REFERENCE_EDGE,
+                IppValue::Keyword("left".to_owned())
+            )]))
+        );
+        assert_eq!(FinishingsCol::from_value(&value), Some(finishings));
+    }
+}
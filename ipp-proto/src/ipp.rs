@@ -1,12 +1,35 @@
 //!
 //! Base IPP definitions and tags
 //!
-use std::fmt;
+use std::{fmt, str::FromStr};
 
 use enum_primitive_derive::Primitive;
+use num_traits::FromPrimitive;
+
+use crate::{attribute::*, IppValue, ParseError};
+
+/// Trait for enums which map to IPP keyword values
+pub trait IppKeyword: Sized {
+    /// Return keyword string for this value
+    fn to_keyword(&self) -> &str;
+
+    /// Create value from keyword string. Returns None if keyword is not recognized
+    fn from_keyword(keyword: &str) -> Option<Self>;
+
+    /// Convert to IppValue::Keyword
+    fn to_value(&self) -> IppValue {
+        IppValue::Keyword(self.to_keyword().to_owned())
+    }
+
+    /// Create value from IppValue::Keyword
+    fn from_value(value: &IppValue) -> Option<Self> {
+        value.as_keyword().and_then(|k| Self::from_keyword(k))
+    }
+}
 
 /// IPP protocol version
-#[derive(Primitive, Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Primitive, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum IppVersion {
     Ipp10 = 0x0100,
     Ipp11 = 0x0101,
@@ -15,7 +38,32 @@ pub enum IppVersion {
     Ipp22 = 0x0202,
 }
 
+/// Keywords used in ipp-versions-supported attribute
+impl IppKeyword for IppVersion {
+    fn to_keyword(&self) -> &str {
+        match self {
+            IppVersion::Ipp10 => "1.0",
+            IppVersion::Ipp11 => "1.1",
+            IppVersion::Ipp20 => "2.0",
+            IppVersion::Ipp21 => "2.1",
+            IppVersion::Ipp22 => "2.2",
+        }
+    }
+
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "1.0" => Some(IppVersion::Ipp10),
+            "1.1" => Some(IppVersion::Ipp11),
+            "2.0" => Some(IppVersion::Ipp20),
+            "2.1" => Some(IppVersion::Ipp21),
+            "2.2" => Some(IppVersion::Ipp22),
+            _ => None,
+        }
+    }
+}
+
 /// IPP operation constants
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Primitive, Debug, Copy, Clone, PartialEq)]
 pub enum Operation {
     PrintJob = 0x0002,
@@ -34,6 +82,10 @@ pub enum Operation {
     PausePrinter = 0x0010,
     ResumePrinter = 0x0011,
     PurgeJobs = 0x0012,
+    SetPrinterAttributes = 0x0013,
+    SetJobAttributes = 0x0014,
+    HoldNewJobs = 0x0025,
+    ReleaseHeldNewJobs = 0x0026,
 
     CupsGetDefault = 0x4001,
     CupsGetPrinters = 0x4002,
@@ -54,6 +106,66 @@ pub enum Operation {
     CupsCreateLocalPrinter = 0x4028,
 }
 
+impl Operation {
+    /// Check whether the operation does not modify printer or job state and can be safely repeated
+    pub fn is_idempotent(self) -> bool {
+        matches!(
+            self,
+            Operation::ValidateJob
+                | Operation::GetJobAttributes
+                | Operation::GetJobs
+                | Operation::GetPrinterAttributes
+                | Operation::CupsGetDefault
+                | Operation::CupsGetPrinters
+                | Operation::CupsGetClasses
+                | Operation::CupsGetDevices
+                | Operation::CupsGetPPDs
+                | Operation::CupsGetPPD
+                | Operation::CupsGetDocument
+        )
+    }
+}
+
+/// Operation code as listed in operations-supported attribute.
+/// Codes which are not known to this crate, for example vendor extensions, are kept as `Unknown`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum OperationCode {
+    Known(Operation),
+    Unknown(u16),
+}
+
+impl OperationCode {
+    /// Create operation code from its numeric value
+    pub fn from_code(code: u16) -> OperationCode {
+        Operation::from_u16(code)
+            .map(OperationCode::Known)
+            .unwrap_or(OperationCode::Unknown(code))
+    }
+
+    /// Return numeric value of the operation code
+    pub fn code(self) -> u16 {
+        match self {
+            OperationCode::Known(operation) => operation as u16,
+            OperationCode::Unknown(code) => code,
+        }
+    }
+
+    /// Return typed operation, None if the code is not known to this crate
+    pub fn operation(self) -> Option<Operation> {
+        match self {
+            OperationCode::Known(operation) => Some(operation),
+            OperationCode::Unknown(_) => None,
+        }
+    }
+}
+
+impl From<Operation> for OperationCode {
+    fn from(operation: Operation) -> Self {
+        OperationCode::Known(operation)
+    }
+}
+
 /// printer-state constants
 #[derive(Primitive, Debug, Copy, Clone, PartialEq)]
 pub enum PrinterState {
@@ -62,6 +174,182 @@ pub enum PrinterState {
     Stopped = 5,
 }
 
+/// Severity suffix of printer-state-reasons keywords
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StateReasonSeverity {
+    /// `-report` suffix: informational
+    Report,
+    /// `-warning` suffix: the printer is still able to print
+    Warning,
+    /// `-error` suffix or no suffix at all, as required by RFC 8011
+    Error,
+}
+
+/// printer-state-reasons keywords without the severity suffix
+#[derive(Debug, Clone, PartialEq)]
+pub enum StateReason {
+    Other,
+    MediaNeeded,
+    MediaJam,
+    MediaLow,
+    MediaEmpty,
+    MovingToPaused,
+    Paused,
+    Shutdown,
+    ConnectingToDevice,
+    TimedOut,
+    Stopping,
+    StoppedPartly,
+    TonerLow,
+    TonerEmpty,
+    SpoolAreaFull,
+    CoverOpen,
+    InterlockOpen,
+    DoorOpen,
+    InputTrayMissing,
+    OutputTrayMissing,
+    OutputAreaAlmostFull,
+    OutputAreaFull,
+    MarkerSupplyLow,
+    MarkerSupplyEmpty,
+    MarkerWasteAlmostFull,
+    MarkerWasteFull,
+    FuserOverTemp,
+    FuserUnderTemp,
+    OpcNearEol,
+    OpcLifeOver,
+    DeveloperLow,
+    DeveloperEmpty,
+    /// Keyword which is not known to this library, for example a vendor extension
+    Unknown(String),
+}
+
+impl IppKeyword for StateReason {
+    fn to_keyword(&self) -> &str {
+        match self {
+            StateReason::Other => "other",
+            StateReason::MediaNeeded => "media-needed",
+            StateReason::MediaJam => "media-jam",
+            StateReason::MediaLow => "media-low",
+            StateReason::MediaEmpty => "media-empty",
+            StateReason::MovingToPaused => "moving-to-paused",
+            StateReason::Paused => "paused",
+            StateReason::Shutdown => "shutdown",
+            StateReason::ConnectingToDevice => "connecting-to-device",
+            StateReason::TimedOut => "timed-out",
+            StateReason::Stopping => "stopping",
+            StateReason::StoppedPartly => "stopped-partly",
+            StateReason::TonerLow => "toner-low",
+            StateReason::TonerEmpty => "toner-empty",
+            StateReason::SpoolAreaFull => "spool-area-full",
+            StateReason::CoverOpen => "cover-open",
+            StateReason::InterlockOpen => "interlock-open",
+            StateReason::DoorOpen => "door-open",
+            StateReason::InputTrayMissing => "input-tray-missing",
+            StateReason::OutputTrayMissing => "output-tray-missing",
+            StateReason::OutputAreaAlmostFull => "output-area-almost-full",
+            StateReason::OutputAreaFull => "output-area-full",
+            StateReason::MarkerSupplyLow => "marker-supply-low",
+            StateReason::MarkerSupplyEmpty => "marker-supply-empty",
+            StateReason::MarkerWasteAlmostFull => "marker-waste-almost-full",
+            StateReason::MarkerWasteFull => "marker-waste-full",
+            StateReason::FuserOverTemp => "fuser-over-temp",
+            StateReason::FuserUnderTemp => "fuser-under-temp",
+            StateReason::OpcNearEol => "opc-near-eol",
+            StateReason::OpcLifeOver => "opc-life-over",
+            StateReason::DeveloperLow => "developer-low",
+            StateReason::DeveloperEmpty => "developer-empty",
+            StateReason::Unknown(ref keyword) => keyword,
+        }
+    }
+
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        Some(match keyword {
+            "other" => StateReason::Other,
+            "media-needed" => StateReason::MediaNeeded,
+            "media-jam" => StateReason::MediaJam,
+            "media-low" => StateReason::MediaLow,
+            "media-empty" => StateReason::MediaEmpty,
+            "moving-to-paused" => StateReason::MovingToPaused,
+            "paused" => StateReason::Paused,
+            "shutdown" => StateReason::Shutdown,
+            "connecting-to-device" => StateReason::ConnectingToDevice,
+            "timed-out" => StateReason::TimedOut,
+            "stopping" => StateReason::Stopping,
+            "stopped-partly" => StateReason::StoppedPartly,
+            "toner-low" => StateReason::TonerLow,
+            "toner-empty" => StateReason::TonerEmpty,
+            "spool-area-full" => StateReason::SpoolAreaFull,
+            "cover-open" => StateReason::CoverOpen,
+            "interlock-open" => StateReason::InterlockOpen,
+            "door-open" => StateReason::DoorOpen,
+            "input-tray-missing" => StateReason::InputTrayMissing,
+            "output-tray-missing" => StateReason::OutputTrayMissing,
+            "output-area-almost-full" => StateReason::OutputAreaAlmostFull,
+            "output-area-full" => StateReason::OutputAreaFull,
+            "marker-supply-low" => StateReason::MarkerSupplyLow,
+            "marker-supply-empty" => StateReason::MarkerSupplyEmpty,
+            "marker-waste-almost-full" => StateReason::MarkerWasteAlmostFull,
+            "marker-waste-full" => StateReason::MarkerWasteFull,
+            "fuser-over-temp" => StateReason::FuserOverTemp,
+            "fuser-under-temp" => StateReason::FuserUnderTemp,
+            "opc-near-eol" => StateReason::OpcNearEol,
+            "opc-life-over" => StateReason::OpcLifeOver,
+            "developer-low" => StateReason::DeveloperLow,
+            "developer-empty" => StateReason::DeveloperEmpty,
+            keyword => StateReason::Unknown(keyword.to_owned()),
+        })
+    }
+}
+
+/// Single value of printer-state-reasons attribute, for example `media-jam-error`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrinterStateReason {
+    /// Reason keyword without the severity suffix
+    pub reason: StateReason,
+    /// Severity taken from the keyword suffix
+    pub severity: StateReasonSeverity,
+}
+
+impl PrinterStateReason {
+    /// Parse printer-state-reasons keyword, splitting off `-report`, `-warning` or `-error` suffix
+    pub fn parse(keyword: &str) -> PrinterStateReason {
+        let (reason, severity) = if let Some(reason) = keyword.strip_suffix("-report") {
+            (reason, StateReasonSeverity::Report)
+        } else if let Some(reason) = keyword.strip_suffix("-warning") {
+            (reason, StateReasonSeverity::Warning)
+        } else if let Some(reason) = keyword.strip_suffix("-error") {
+            (reason, StateReasonSeverity::Error)
+        } else {
+            (keyword, StateReasonSeverity::Error)
+        };
+
+        PrinterStateReason {
+            reason: StateReason::from_keyword(reason).unwrap_or_else(|| StateReason::Unknown(reason.to_owned())),
+            severity,
+        }
+    }
+
+    /// Check if this reason prevents the printer from printing new jobs,
+    /// for example a paper jam or an open cover reported with error severity
+    pub fn is_error(&self) -> bool {
+        self.severity == StateReasonSeverity::Error
+            && matches!(
+                self.reason,
+                StateReason::MediaJam
+                    | StateReason::TonerEmpty
+                    | StateReason::SpoolAreaFull
+                    | StateReason::CoverOpen
+                    | StateReason::DoorOpen
+                    | StateReason::InputTrayMissing
+                    | StateReason::OutputTrayMissing
+                    | StateReason::MarkerSupplyEmpty
+                    | StateReason::Paused
+                    | StateReason::Shutdown
+            )
+    }
+}
+
 /// paper orientation constants
 #[derive(Primitive, Debug, Copy, Clone, PartialEq)]
 pub enum Orientation {
@@ -91,6 +379,196 @@ pub enum Finishings {
     EdgeStitch = 9,
 }
 
+/// sides keywords
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Sides {
+    OneSided,
+    TwoSidedLongEdge,
+    TwoSidedShortEdge,
+}
+
+impl IppKeyword for Sides {
+    fn to_keyword(&self) -> &str {
+        match self {
+            Sides::OneSided => "one-sided",
+            Sides::TwoSidedLongEdge => "two-sided-long-edge",
+            Sides::TwoSidedShortEdge => "two-sided-short-edge",
+        }
+    }
+
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "one-sided" => Some(Sides::OneSided),
+            "two-sided-long-edge" => Some(Sides::TwoSidedLongEdge),
+            "two-sided-short-edge" => Some(Sides::TwoSidedShortEdge),
+            _ => None,
+        }
+    }
+}
+
+/// print-color-mode keywords
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PrintColorMode {
+    Auto,
+    AutoMonochrome,
+    BiLevel,
+    Color,
+    Highlight,
+    Monochrome,
+    ProcessBiLevel,
+    ProcessMonochrome,
+}
+
+impl IppKeyword for PrintColorMode {
+    fn to_keyword(&self) -> &str {
+        match self {
+            PrintColorMode::Auto => "auto",
+            PrintColorMode::AutoMonochrome => "auto-monochrome",
+            PrintColorMode::BiLevel => "bi-level",
+            PrintColorMode::Color => "color",
+            PrintColorMode::Highlight => "highlight",
+            PrintColorMode::Monochrome => "monochrome",
+            PrintColorMode::ProcessBiLevel => "process-bi-level",
+            PrintColorMode::ProcessMonochrome => "process-monochrome",
+        }
+    }
+
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "auto" => Some(PrintColorMode::Auto),
+            "auto-monochrome" => Some(PrintColorMode::AutoMonochrome),
+            "bi-level" => Some(PrintColorMode::BiLevel),
+            "color" => Some(PrintColorMode::Color),
+            "highlight" => Some(PrintColorMode::Highlight),
+            "monochrome" => Some(PrintColorMode::Monochrome),
+            "process-bi-level" => Some(PrintColorMode::ProcessBiLevel),
+            "process-monochrome" => Some(PrintColorMode::ProcessMonochrome),
+            _ => None,
+        }
+    }
+}
+
+/// printer-kind keywords
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrinterKind {
+    Disc,
+    Document,
+    Envelope,
+    Label,
+    LargeFormat,
+    Photo,
+    Postcard,
+    Receipt,
+    Roll,
+    /// Any other keyword, for example a vendor extension
+    Other(String),
+}
+
+impl IppKeyword for PrinterKind {
+    fn to_keyword(&self) -> &str {
+        match self {
+            PrinterKind::Disc => "disc",
+            PrinterKind::Document => "document",
+            PrinterKind::Envelope => "envelope",
+            PrinterKind::Label => "label",
+            PrinterKind::LargeFormat => "large-format",
+            PrinterKind::Photo => "photo",
+            PrinterKind::Postcard => "postcard",
+            PrinterKind::Receipt => "receipt",
+            PrinterKind::Roll => "roll",
+            PrinterKind::Other(keyword) => keyword,
+        }
+    }
+
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        Some(match keyword {
+            "disc" => PrinterKind::Disc,
+            "document" => PrinterKind::Document,
+            "envelope" => PrinterKind::Envelope,
+            "label" => PrinterKind::Label,
+            "large-format" => PrinterKind::LargeFormat,
+            "photo" => PrinterKind::Photo,
+            "postcard" => PrinterKind::Postcard,
+            "receipt" => PrinterKind::Receipt,
+            "roll" => PrinterKind::Roll,
+            other => PrinterKind::Other(other.to_owned()),
+        })
+    }
+}
+
+/// Commonly used requested-attributes values: attribute names and group keywords
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RequestedAttribute {
+    /// `all` group keyword
+    All,
+    /// `job-template` group keyword
+    JobTemplate,
+    /// `printer-description` group keyword
+    PrinterDescription,
+    /// `media-col-database` group keyword
+    MediaColDatabase,
+    ColorSupported,
+    DocumentFormatSupported,
+    MediaColReady,
+    MediaDefault,
+    MediaSupported,
+    OperationsSupported,
+    PrintColorModeSupported,
+    PrinterInfo,
+    PrinterIsAcceptingJobs,
+    PrinterLocation,
+    PrinterMakeAndModel,
+    PrinterName,
+    PrinterState,
+    PrinterStateMessage,
+    PrinterStateReasons,
+    PrinterUriSupported,
+    QueuedJobCount,
+    SidesSupported,
+}
+
+const REQUESTED_ATTRIBUTE_KEYWORDS: &[(RequestedAttribute, &str)] = &[
+    (RequestedAttribute::All, "all"),
+    (RequestedAttribute::JobTemplate, "job-template"),
+    (RequestedAttribute::PrinterDescription, "printer-description"),
+    (RequestedAttribute::MediaColDatabase, "media-col-database"),
+    (RequestedAttribute::ColorSupported, COLOR_SUPPORTED),
+    (RequestedAttribute::DocumentFormatSupported, DOCUMENT_FORMAT_SUPPORTED),
+    (RequestedAttribute::MediaColReady, MEDIA_COL_READY),
+    (RequestedAttribute::MediaDefault, MEDIA_DEFAULT),
+    (RequestedAttribute::MediaSupported, MEDIA_SUPPORTED),
+    (RequestedAttribute::OperationsSupported, OPERATIONS_SUPPORTED),
+    (RequestedAttribute::PrintColorModeSupported, PRINT_COLOR_MODE_SUPPORTED),
+    (RequestedAttribute::PrinterInfo, PRINTER_INFO),
+    (RequestedAttribute::PrinterIsAcceptingJobs, PRINTER_IS_ACCEPTING_JOBS),
+    (RequestedAttribute::PrinterLocation, PRINTER_LOCATION),
+    (RequestedAttribute::PrinterMakeAndModel, PRINTER_MAKE_AND_MODEL),
+    (RequestedAttribute::PrinterName, PRINTER_NAME),
+    (RequestedAttribute::PrinterState, PRINTER_STATE),
+    (RequestedAttribute::PrinterStateMessage, PRINTER_STATE_MESSAGE),
+    (RequestedAttribute::PrinterStateReasons, PRINTER_STATE_REASONS),
+    (RequestedAttribute::PrinterUriSupported, PRINTER_URI_SUPPORTED),
+    (RequestedAttribute::QueuedJobCount, QUEUED_JOB_COUNT),
+    (RequestedAttribute::SidesSupported, SIDES_SUPPORTED),
+];
+
+impl IppKeyword for RequestedAttribute {
+    fn to_keyword(&self) -> &str {
+        REQUESTED_ATTRIBUTE_KEYWORDS
+            .iter()
+            .find(|(attr, _)| attr == self)
+            .map(|(_, keyword)| *keyword)
+            .unwrap_or_default()
+    }
+
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        REQUESTED_ATTRIBUTE_KEYWORDS
+            .iter()
+            .find(|(_, k)| *k == keyword)
+            .map(|(attr, _)| *attr)
+    }
+}
+
 /// job-state constants
 #[derive(Primitive, Debug, Copy, Clone, PartialEq)]
 pub enum JobState {
@@ -103,7 +581,16 @@ pub enum JobState {
     Completed = 9,
 }
 
+impl JobState {
+    /// Check whether the job has finished and its state will not change anymore:
+    /// canceled, aborted or completed
+    pub fn is_terminal(self) -> bool {
+        matches!(self, JobState::Canceled | JobState::Aborted | JobState::Completed)
+    }
+}
+
 /// group delimiter tags
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Primitive, Debug, Copy, Clone, PartialEq, Hash, Eq)]
 pub enum DelimiterTag {
     OperationAttributes = 0x01,
@@ -113,12 +600,42 @@ pub enum DelimiterTag {
     UnsupportedAttributes = 0x05,
 }
 
+impl fmt::Display for DelimiterTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DelimiterTag::OperationAttributes => write!(f, "operation-attributes"),
+            DelimiterTag::JobAttributes => write!(f, "job-attributes"),
+            DelimiterTag::EndOfAttributes => write!(f, "end-of-attributes"),
+            DelimiterTag::PrinterAttributes => write!(f, "printer-attributes"),
+            DelimiterTag::UnsupportedAttributes => write!(f, "unsupported-attributes"),
+        }
+    }
+}
+
+impl FromStr for DelimiterTag {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "operation-attributes" => Ok(DelimiterTag::OperationAttributes),
+            "job-attributes" => Ok(DelimiterTag::JobAttributes),
+            "end-of-attributes" => Ok(DelimiterTag::EndOfAttributes),
+            "printer-attributes" => Ok(DelimiterTag::PrinterAttributes),
+            "unsupported-attributes" => Ok(DelimiterTag::UnsupportedAttributes),
+            _ => Err(ParseError::InvalidValue(s.to_owned())),
+        }
+    }
+}
+
 /// IPP value tags
 #[derive(Primitive, Debug, Copy, Clone, PartialEq)]
 pub enum ValueTag {
     Unsupported = 0x10,
     Unknown = 0x12,
     NoValue = 0x13,
+    NotSettable = 0x15,
+    DeleteAttribute = 0x16,
+    AdminDefine = 0x17,
     Integer = 0x21,
     Boolean = 0x22,
     Enum = 0x23,
@@ -147,6 +664,9 @@ pub enum StatusCode {
     SuccessfulOK = 0x0000,
     SuccessfulOKIgnoredOrSubstitutedAttributes = 0x0001,
     SuccessfulOKConflictingAttributes = 0x0002,
+    SuccessfulOKIgnoredSubscriptions = 0x0003,
+    SuccessfulOKTooManyEvents = 0x0005,
+    SuccessfulOKEventsComplete = 0x0007,
     ClientErrorBadRequest = 0x0400,
     ClientErrorForbidden = 0x0401,
     ClientErrorNotAuthenticated = 0x0402,
@@ -178,12 +698,22 @@ pub enum StatusCode {
     ServerErrorMultipleDocumentJobsNotSupported = 0x0509,
 }
 
+impl StatusCode {
+    /// Check whether the status is one of successful-ok codes
+    pub fn is_successful(self) -> bool {
+        (self as u16) < 0x0100
+    }
+}
+
 impl fmt::Display for StatusCode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             StatusCode::SuccessfulOK => write!(f, "No error"),
             StatusCode::SuccessfulOKIgnoredOrSubstitutedAttributes => write!(f, "Ignored or substituted attributes"),
             StatusCode::SuccessfulOKConflictingAttributes => write!(f, "Conflicting attributes"),
+            StatusCode::SuccessfulOKIgnoredSubscriptions => write!(f, "Ignored subscriptions"),
+            StatusCode::SuccessfulOKTooManyEvents => write!(f, "Too many events"),
+            StatusCode::SuccessfulOKEventsComplete => write!(f, "Events complete"),
             StatusCode::ClientErrorBadRequest => write!(f, "Bad request"),
             StatusCode::ClientErrorForbidden => write!(f, "Forbidden"),
             StatusCode::ClientErrorNotAuthenticated => write!(f, "Not authenticated"),
@@ -218,3 +748,49 @@ impl fmt::Display for StatusCode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyword_round_trip() {
+        for sides in &[Sides::OneSided, Sides::TwoSidedLongEdge, Sides::TwoSidedShortEdge] {
+            assert_eq!(Sides::from_keyword(sides.to_keyword()), Some(*sides));
+            assert_eq!(Sides::from_value(&sides.to_value()), Some(*sides));
+        }
+        assert_eq!(
+            PrintColorMode::from_value(&IppValue::Keyword("monochrome".to_owned())),
+            Some(PrintColorMode::Monochrome)
+        );
+        assert_eq!(PrintColorMode::from_keyword("rainbow"), None);
+        assert_eq!(Sides::from_value(&IppValue::Integer(1)), None);
+        assert_eq!(IppVersion::from_keyword("2.0"), Some(IppVersion::Ipp20));
+        assert_eq!(IppVersion::Ipp11.to_keyword(), "1.1");
+    }
+
+    #[test]
+    fn test_requested_attribute_keywords() {
+        for (attr, keyword) in REQUESTED_ATTRIBUTE_KEYWORDS {
+            assert_eq!(attr.to_keyword(), *keyword);
+            assert_eq!(RequestedAttribute::from_keyword(keyword), Some(*attr));
+        }
+        assert_eq!(RequestedAttribute::PrinterState.to_keyword(), "printer-state");
+        assert_eq!(RequestedAttribute::from_keyword("printer-stat"), None);
+    }
+
+    #[test]
+    fn test_delimiter_tag_round_trip() {
+        for tag in &[
+            DelimiterTag::OperationAttributes,
+            DelimiterTag::JobAttributes,
+            DelimiterTag::EndOfAttributes,
+            DelimiterTag::PrinterAttributes,
+            DelimiterTag::UnsupportedAttributes,
+        ] {
+            assert_eq!(tag.to_string().parse::<DelimiterTag>().ok(), Some(*tag));
+        }
+        assert_eq!(DelimiterTag::PrinterAttributes.to_string(), "printer-attributes");
+        assert!("printer".parse::<DelimiterTag>().is_err());
+    }
+}
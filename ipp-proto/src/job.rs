@@ -0,0 +1,322 @@
+//!
+//! Job information helpers
+//!
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
+
+use num_traits::FromPrimitive;
+
+use crate::{attribute::*, ipp::*, IppAttribute, IppAttributeGroup, IppAttributes, IppValue};
+
+// Explanations for the job-state-reasons keywords of canceled and aborted jobs
+const FAILURE_REASONS: &[(&str, &str)] = &[
+    ("job-canceled-by-user", "The job was canceled by the user"),
+    ("job-canceled-by-operator", "The job was canceled by the operator"),
+    ("job-canceled-at-device", "The job was canceled at the printer"),
+    ("aborted-by-system", "The job was aborted by the printer"),
+    (
+        "unsupported-document-format",
+        "The document format is not supported by the printer",
+    ),
+    (
+        "unsupported-compression",
+        "The document compression is not supported by the printer",
+    ),
+    (
+        "document-format-error",
+        "The document could not be processed because of a format error",
+    ),
+    ("document-unprintable-error", "The document could not be printed"),
+    ("compression-error", "The compressed document could not be decompressed"),
+    ("document-access-error", "The printer could not access the document"),
+    ("document-password-error", "The document password is incorrect"),
+    ("document-permission-error", "The document does not permit printing"),
+    (
+        "job-data-insufficient",
+        "The printer did not receive enough document data",
+    ),
+    ("submission-interrupted", "The job submission was interrupted"),
+];
+
+/// Job information extracted from the job attributes group
+#[derive(Clone, Debug)]
+pub struct JobInfo {
+    attributes: HashMap<String, IppAttribute>,
+}
+
+impl JobInfo {
+    /// Create job information from the attribute group
+    pub fn from_group(group: &IppAttributeGroup) -> JobInfo {
+        JobInfo {
+            attributes: group.attributes().clone(),
+        }
+    }
+
+    /// Create a list of jobs from all job attribute groups in the response,
+    /// for example from Get-Jobs operation
+    pub fn list(attrs: &IppAttributes) -> Vec<JobInfo> {
+        attrs
+            .groups_of(DelimiterTag::JobAttributes)
+            .into_iter()
+            .map(JobInfo::from_group)
+            .collect()
+    }
+
+    /// Return all job attributes
+    pub fn attributes(&self) -> &HashMap<String, IppAttribute> {
+        &self.attributes
+    }
+
+    fn value(&self, name: &str) -> Option<&IppValue> {
+        self.attributes.get(name).map(|attr| attr.value())
+    }
+
+    fn seconds(&self, name: &str) -> Option<Duration> {
+        self.value(name)
+            .and_then(|v| v.as_integer())
+            .filter(|v| **v >= 0)
+            .map(|v| Duration::from_secs(*v as u64))
+    }
+
+    fn date_time(&self, name: &str) -> Option<SystemTime> {
+        self.value(name).and_then(|v| v.to_system_time())
+    }
+
+    /// Return job-id attribute
+    pub fn id(&self) -> Option<i32> {
+        self.value(JOB_ID).and_then(|v| v.as_integer()).cloned()
+    }
+
+    /// Return job-uuid attribute without the `urn:uuid:` prefix
+    pub fn uuid(&self) -> Option<&str> {
+        self.value(JOB_UUID).and_then(|v| v.as_str()).map(strip_uuid_urn)
+    }
+
+    /// Return job-name attribute
+    pub fn name(&self) -> Option<&str> {
+        self.value(JOB_NAME).and_then(|v| v.as_str())
+    }
+
+    /// Return job-uri attribute
+    pub fn uri(&self) -> Option<&str> {
+        self.value(JOB_URI).and_then(|v| v.as_str())
+    }
+
+    /// Return job-state attribute
+    pub fn state(&self) -> Option<JobState> {
+        self.value(JOB_STATE)
+            .and_then(|v| v.as_enum())
+            .and_then(|v| JobState::from_i32(*v))
+    }
+
+    /// Return job-state-reasons attribute
+    pub fn state_reasons(&self) -> Vec<&str> {
+        self.value(JOB_STATE_REASONS)
+            .map(|v| v.into_iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Return number-of-documents attribute: the number of documents in the job known to the printer
+    pub fn number_of_documents(&self) -> Option<i32> {
+        self.value(NUMBER_OF_DOCUMENTS).and_then(|v| v.as_integer()).cloned()
+    }
+
+    /// Return job-k-octets-processed attribute as a 64-bit counter, see `IppValue::as_i64`
+    pub fn k_octets_processed(&self) -> Option<i64> {
+        self.value(JOB_K_OCTETS_PROCESSED).and_then(|v| v.as_i64())
+    }
+
+    /// Return job-impressions-completed attribute as a 64-bit counter, see `IppValue::as_i64`
+    pub fn impressions_completed(&self) -> Option<i64> {
+        self.value(JOB_IMPRESSIONS_COMPLETED).and_then(|v| v.as_i64())
+    }
+
+    /// Return time-at-creation attribute: printer uptime when the job was created
+    pub fn time_at_creation(&self) -> Option<Duration> {
+        self.seconds(TIME_AT_CREATION)
+    }
+
+    /// Return time-at-processing attribute: printer uptime when the job started processing
+    pub fn time_at_processing(&self) -> Option<Duration> {
+        self.seconds(TIME_AT_PROCESSING)
+    }
+
+    /// Return time-at-completed attribute: printer uptime when the job was completed
+    pub fn time_at_completed(&self) -> Option<Duration> {
+        self.seconds(TIME_AT_COMPLETED)
+    }
+
+    /// Return date-time-at-creation attribute
+    pub fn date_time_at_creation(&self) -> Option<SystemTime> {
+        self.date_time(DATE_TIME_AT_CREATION)
+    }
+
+    /// Return date-time-at-processing attribute
+    pub fn date_time_at_processing(&self) -> Option<SystemTime> {
+        self.date_time(DATE_TIME_AT_PROCESSING)
+    }
+
+    /// Return date-time-at-completed attribute
+    pub fn date_time_at_completed(&self) -> Option<SystemTime> {
+        self.date_time(DATE_TIME_AT_COMPLETED)
+    }
+
+    /// Return a human-readable explanation why the job was not completed. Returns None unless
+    /// job-state is canceled or aborted. Known job-state-reasons keywords are explained,
+    /// unknown ones are listed after a generic description
+    pub fn failure_reason(&self) -> Option<String> {
+        let generic = match self.state()? {
+            JobState::Canceled => "The job was canceled",
+            JobState::Aborted => "The job was aborted",
+            _ => return None,
+        };
+
+        let reasons = self.state_reasons();
+        if let Some(explanation) = reasons
+            .iter()
+            .filter_map(|reason| FAILURE_REASONS.iter().find(|r| r.0 == *reason))
+            .map(|r| r.1)
+            .next()
+        {
+            return Some(explanation.to_owned());
+        }
+
+        let other = reasons
+            .into_iter()
+            .filter(|reason| *reason != "none" && !reason.starts_with("job-completed"))
+            .collect::<Vec<_>>();
+        if other.is_empty() {
+            Some(generic.to_owned())
+        } else {
+            Some(format!("{} ({})", generic, other.join(", ")))
+        }
+    }
+
+    /// Return time spent from the start of processing to completion.
+    /// Absolute date-time attributes are preferred, otherwise the uptime-based attributes are used
+    pub fn processing_duration(&self) -> Option<Duration> {
+        if let (Some(processing), Some(completed)) = (self.date_time_at_processing(), self.date_time_at_completed()) {
+            return completed.duration_since(processing).ok();
+        }
+
+        match (self.time_at_processing(), self.time_at_completed()) {
+            (Some(processing), Some(completed)) => completed.checked_sub(processing),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use super::*;
+
+    fn job_group(attrs: Vec<IppAttribute>) -> IppAttributeGroup {
+        let mut group = IppAttributeGroup::new(DelimiterTag::JobAttributes);
+        for attr in attrs {
+            group.attributes_mut().insert(attr.name().to_owned(), attr);
+        }
+        group
+    }
+
+    #[test]
+    fn test_job_info() {
+        let group = job_group(vec![
+            IppAttribute::new(JOB_ID, IppValue::Integer(42)),
+            IppAttribute::new(JOB_STATE, IppValue::Enum(JobState::Completed as i32)),
+            IppAttribute::new(
+                JOB_STATE_REASONS,
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("job-completed-successfully".to_owned()),
+                    IppValue::Keyword("job-printed-successfully".to_owned()),
+                ]),
+            ),
+        ]);
+
+        let job = JobInfo::from_group(&group);
+        assert_eq!(job.id(), Some(42));
+        assert_eq!(job.state(), Some(JobState::Completed));
+        assert_eq!(
+            job.state_reasons(),
+            vec!["job-completed-successfully", "job-printed-successfully"]
+        );
+        assert_eq!(job.name(), None);
+    }
+
+    #[test]
+    fn test_failure_reason() {
+        let job = |state: JobState, reasons: &[&str]| {
+            JobInfo::from_group(&job_group(vec![
+                IppAttribute::new(JOB_STATE, IppValue::Enum(state as i32)),
+                IppAttribute::new(
+                    JOB_STATE_REASONS,
+                    IppValue::ListOf(reasons.iter().map(|r| IppValue::Keyword((*r).to_owned())).collect()),
+                ),
+            ]))
+        };
+
+        assert_eq!(
+            job(JobState::Completed, &["job-completed-successfully"]).failure_reason(),
+            None
+        );
+        assert_eq!(
+            job(
+                JobState::Canceled,
+                &["job-canceled-by-user", "job-completed-with-errors"]
+            )
+            .failure_reason(),
+            Some("The job was canceled by the user".to_owned())
+        );
+        assert_eq!(
+            job(JobState::Aborted, &["job-completed-with-errors", "aborted-by-system"]).failure_reason(),
+            Some("The job was aborted by the printer".to_owned())
+        );
+        assert_eq!(
+            job(JobState::Aborted, &["vendor-paper-path-error"]).failure_reason(),
+            Some("The job was aborted (vendor-paper-path-error)".to_owned())
+        );
+        assert_eq!(
+            job(JobState::Canceled, &["none"]).failure_reason(),
+            Some("The job was canceled".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_job_counters() {
+        let group = job_group(vec![
+            IppAttribute::new(JOB_K_OCTETS_PROCESSED, IppValue::OctetString("3000000000".to_owned())),
+            IppAttribute::new(JOB_IMPRESSIONS_COMPLETED, IppValue::Integer(12)),
+        ]);
+
+        let job = JobInfo::from_group(&group);
+        assert_eq!(job.k_octets_processed(), Some(3_000_000_000));
+        assert_eq!(job.impressions_completed(), Some(12));
+    }
+
+    #[test]
+    fn test_job_times() {
+        let group = job_group(vec![
+            IppAttribute::new(TIME_AT_CREATION, IppValue::Integer(100)),
+            IppAttribute::new(TIME_AT_PROCESSING, IppValue::Integer(110)),
+            IppAttribute::new(TIME_AT_COMPLETED, IppValue::Integer(150)),
+        ]);
+        let job = JobInfo::from_group(&group);
+        assert_eq!(job.time_at_creation(), Some(Duration::from_secs(100)));
+        assert_eq!(job.date_time_at_processing(), None);
+        assert_eq!(job.processing_duration(), Some(Duration::from_secs(40)));
+
+        let start = UNIX_EPOCH + Duration::from_secs(1_500_000_000);
+        let group = job_group(vec![
+            IppAttribute::new(TIME_AT_PROCESSING, IppValue::Integer(110)),
+            IppAttribute::new(TIME_AT_COMPLETED, IppValue::Integer(150)),
+            IppAttribute::new(DATE_TIME_AT_PROCESSING, IppValue::from(start)),
+            IppAttribute::new(DATE_TIME_AT_COMPLETED, IppValue::from(start + Duration::from_secs(30))),
+        ]);
+        let job = JobInfo::from_group(&group);
+        assert_eq!(job.date_time_at_processing(), Some(start));
+        assert_eq!(job.processing_duration(), Some(Duration::from_secs(30)));
+    }
+}
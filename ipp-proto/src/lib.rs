@@ -1,4 +1,7 @@
-use std::io::{self, Read, Write};
+use std::{
+    cmp,
+    io::{self, Read, Write},
+};
 
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use bytes::{Bytes, BytesMut};
@@ -9,30 +12,112 @@ use tokio::io::AsyncRead;
 pub use crate::{
     attribute::{IppAttribute, IppAttributeGroup, IppAttributes},
     builder::{
-        CreateJobBuilder, GetPrinterAttributesBuilder, IppOperationBuilder, PrintJobBuilder, SendDocumentBuilder,
+        CreateJobBuilder, GetJobAttributesBuilder, GetJobsBuilder, GetPrinterAttributesBuilder, IppOperationBuilder,
+        PausePrinterBuilder, PrintJobBuilder, PrintUriBuilder, ResumePrinterBuilder, SendDocumentBuilder,
+        SetJobAttributesBuilder, SetPrinterAttributesBuilder, ValidateJobBuilder,
     },
-    ipp::{IppVersion, Operation, StatusCode},
-    parser::{AsyncIppParser, IppParser, ParseError},
+    finishings::{FinishingPosition, FinishingsCol, FinishingsColBuilder},
+    ipp::{IppKeyword, IppVersion, Operation, PrinterKind, RequestedAttribute, StatusCode},
+    job::JobInfo,
+    media::{MediaCol, MediaColBuilder, MediaSize},
+    parser::{AsyncIppParser, AsyncIppStreamParser, IppParser, ParseError},
+    printer::{DeviceIdentity, PrinterCapabilities, PrinterInfo},
     request::{IppRequestResponse, PayloadKind},
+    tray::Tray,
     value::IppValue,
 };
 
 pub mod attribute;
 pub mod builder;
+pub mod finishings;
 pub mod ipp;
+pub mod job;
+pub mod media;
 pub mod operation;
 pub mod parser;
+pub mod printer;
 pub mod request;
+pub mod tray;
 pub mod value;
 
+/// HTTP transfer mode for the document data
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransferMode {
+    /// Document data is streamed using chunked Transfer-Encoding
+    Chunked,
+    /// Document data is collected in memory and sent with exact Content-Length
+    Buffered,
+}
+
+/// Callback which receives the number of document bytes streamed so far
+pub type ProgressCallback = Box<dyn Fn(u64) + Send>;
+
 /// Source for IPP data stream (job file)
 pub struct IppJobSource {
     inner: Box<dyn AsyncRead + Send>,
     buffer: Vec<u8>,
+    mode: TransferMode,
+    progress: Option<ProgressCallback>,
+    bytes_read: u64,
 }
 
 impl IppJobSource {
-    const CHUNK_SIZE: usize = 32768;
+    /// Default size of the chunks read from the source
+    pub const DEFAULT_CHUNK_SIZE: usize = 65536;
+
+    /// Create job source from in-memory data. Default transfer mode is `TransferMode::Buffered`
+    pub fn from_bytes<B>(data: B) -> IppJobSource
+    where
+        B: Into<Bytes>,
+    {
+        let mut source = IppJobSource::from(io::Cursor::new(data.into()));
+        source.mode = TransferMode::Buffered;
+        source
+    }
+
+    /// Create job source from a stream of data chunks. Default transfer mode is `TransferMode::Chunked`
+    pub fn from_stream<S>(stream: S) -> IppJobSource
+    where
+        S: Stream<Error = io::Error> + Send + 'static,
+        S::Item: AsRef<[u8]> + Send,
+    {
+        IppJobSource::from(StreamReader {
+            stream,
+            chunk: None,
+            pos: 0,
+        })
+    }
+
+    /// Get transfer mode
+    pub fn transfer_mode(&self) -> TransferMode {
+        self.mode
+    }
+
+    /// Set transfer mode
+    pub fn set_transfer_mode(&mut self, mode: TransferMode) {
+        self.mode = mode;
+    }
+
+    /// Get maximum size of the chunks read from the source
+    pub fn chunk_size(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Set maximum size of the chunks read from the source and sent to the printer.
+    /// Larger chunks reduce the per-chunk overhead for big documents. Default is 64 KiB
+    pub fn set_chunk_size(&mut self, size: usize) {
+        self.buffer.resize(cmp::max(size, 1), 0);
+    }
+
+    /// Set callback which is invoked with the cumulative number of document bytes after every chunk
+    /// taken from the source. With `TransferMode::Chunked` chunks are taken as the connection sends them,
+    /// with `TransferMode::Buffered` the document is collected before the request is sent
+    pub fn set_progress_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(u64) + Send + 'static,
+    {
+        self.progress = Some(Box::new(callback));
+    }
 }
 
 impl Stream for IppJobSource {
@@ -42,6 +127,10 @@ impl Stream for IppJobSource {
     fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
         let size = try_ready!(self.inner.poll_read(&mut self.buffer));
         if size > 0 {
+            self.bytes_read += size as u64;
+            if let Some(ref progress) = self.progress {
+                progress(self.bytes_read);
+            }
             Ok(Async::Ready(Some(self.buffer[0..size].into())))
         } else {
             Ok(Async::Ready(None))
@@ -53,15 +142,61 @@ impl<T> From<T> for IppJobSource
 where
     T: 'static + AsyncRead + Send,
 {
-    /// Create job source from AsyncRead
+    /// Create job source from AsyncRead. Default transfer mode is `TransferMode::Chunked`
     fn from(r: T) -> Self {
         IppJobSource {
             inner: Box::new(r),
-            buffer: vec![0; IppJobSource::CHUNK_SIZE],
+            buffer: vec![0; IppJobSource::DEFAULT_CHUNK_SIZE],
+            mode: TransferMode::Chunked,
+            progress: None,
+            bytes_read: 0,
+        }
+    }
+}
+
+// Adapter which turns a stream of data chunks into AsyncRead
+struct StreamReader<S: Stream> {
+    stream: S,
+    chunk: Option<S::Item>,
+    pos: usize,
+}
+
+impl<S> Read for StreamReader<S>
+where
+    S: Stream<Error = io::Error>,
+    S::Item: AsRef<[u8]>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if let Some(ref chunk) = self.chunk {
+                let data = &chunk.as_ref()[self.pos..];
+                if !data.is_empty() {
+                    let size = cmp::min(buf.len(), data.len());
+                    buf[..size].copy_from_slice(&data[..size]);
+                    self.pos += size;
+                    return Ok(size);
+                }
+            }
+
+            match self.stream.poll()? {
+                Async::Ready(Some(chunk)) => {
+                    self.chunk = Some(chunk);
+                    self.pos = 0;
+                }
+                Async::Ready(None) => return Ok(0),
+                Async::NotReady => return Err(io::ErrorKind::WouldBlock.into()),
+            }
         }
     }
 }
 
+impl<S> AsyncRead for StreamReader<S>
+where
+    S: Stream<Error = io::Error>,
+    S::Item: AsRef<[u8]>,
+{
+}
+
 pub(crate) trait IppWriter {
     fn write(&self, writer: &mut dyn Write) -> io::Result<usize>;
 }
@@ -83,7 +218,8 @@ pub(crate) trait IppReadExt: Read {
 impl<R: io::Read + ?Sized> IppReadExt for R {}
 
 /// IPP request and response header
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IppHeader {
     /// IPP protocol version
     pub version: IppVersion,
@@ -117,6 +253,17 @@ impl IppHeader {
     pub fn operation(&self) -> Result<Operation, StatusCode> {
         Operation::from_u16(self.operation_status).ok_or(StatusCode::ServerErrorOperationNotSupported)
     }
+
+    /// Get operation_status field of a response as StatusCode enum, None for unknown status codes
+    pub fn status_code(&self) -> Option<StatusCode> {
+        StatusCode::from_u16(self.operation_status)
+    }
+
+    /// Check whether the response status is one of successful-ok codes (0x0000-0x00ff),
+    /// including the ones not known to this crate
+    pub fn is_successful(&self) -> bool {
+        self.operation_status < 0x0100
+    }
 }
 
 impl IppWriter for IppHeader {
@@ -134,6 +281,8 @@ impl IppWriter for IppHeader {
 mod tests {
     use std::io::Cursor;
 
+    use futures::Future;
+
     use super::*;
 
     #[test]
@@ -161,6 +310,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_job_source_chunk_size() {
+        let mut source = IppJobSource::from_bytes(vec![0u8; 10000]);
+        assert_eq!(source.chunk_size(), IppJobSource::DEFAULT_CHUNK_SIZE);
+
+        source.set_chunk_size(4096);
+        let chunks = source.map(|chunk| chunk.len()).collect().wait().unwrap();
+        assert_eq!(chunks, vec![4096, 4096, 1808]);
+    }
+
     #[test]
     fn test_write_header() {
         let header = IppHeader::new(IppVersion::Ipp21, 0x1234, 0xaa55aa55);
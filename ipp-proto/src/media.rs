@@ -0,0 +1,228 @@
+//!
+//! Media collection helpers
+//!
+use crate::{attribute::*, IppValue};
+
+/// Media size in hundredths of millimeters
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MediaSize {
+    /// x-dimension member
+    pub x_dimension: i32,
+    /// y-dimension member
+    pub y_dimension: i32,
+}
+
+/// Media collection (media-col), for example from media-col-ready printer attribute
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MediaCol {
+    /// media-size member
+    pub size: Option<MediaSize>,
+    /// media-source member, for example `tray-1`
+    pub source: Option<String>,
+    /// media-type member, for example `stationery`
+    pub media_type: Option<String>,
+    /// media-top-margin member in hundredths of millimeters
+    pub top_margin: Option<i32>,
+    /// media-bottom-margin member in hundredths of millimeters
+    pub bottom_margin: Option<i32>,
+    /// media-left-margin member in hundredths of millimeters
+    pub left_margin: Option<i32>,
+    /// media-right-margin member in hundredths of millimeters
+    pub right_margin: Option<i32>,
+}
+
+fn integer_member(value: &IppValue, name: &str) -> Option<i32> {
+    value.member(name).and_then(|v| v.as_integer().cloned())
+}
+
+fn string_member(value: &IppValue, name: &str) -> Option<String> {
+    value.member(name).and_then(|v| v.as_str().map(ToOwned::to_owned))
+}
+
+impl MediaCol {
+    /// Parse media collection from the collection value. Returns None for other value types.
+    pub fn from_value(value: &IppValue) -> Option<MediaCol> {
+        value.as_collection()?;
+
+        let size = value.member(MEDIA_SIZE).and_then(|size| {
+            match (integer_member(&size, X_DIMENSION), integer_member(&size, Y_DIMENSION)) {
+                (Some(x_dimension), Some(y_dimension)) => Some(MediaSize {
+                    x_dimension,
+                    y_dimension,
+                }),
+                _ => None,
+            }
+        });
+
+        Some(MediaCol {
+            size,
+            source: string_member(value, MEDIA_SOURCE),
+            media_type: string_member(value, MEDIA_TYPE),
+            top_margin: integer_member(value, MEDIA_TOP_MARGIN),
+            bottom_margin: integer_member(value, MEDIA_BOTTOM_MARGIN),
+            left_margin: integer_member(value, MEDIA_LEFT_MARGIN),
+            right_margin: integer_member(value, MEDIA_RIGHT_MARGIN),
+        })
+    }
+
+    /// Convert media collection into the media-col collection value. Unset members are omitted.
+    pub fn to_value(&self) -> IppValue {
+        let mut members = Vec::new();
+        if let Some(ref size) = self.size {
+            members.push((
+                MEDIA_SIZE,
+                IppValue::collection(vec![
+                    (X_DIMENSION, IppValue::Integer(size.x_dimension)),
+                    (Y_DIMENSION, IppValue::Integer(size.y_dimension)),
+                ]),
+            ));
+        }
+        if let Some(ref source) = self.source {
+            members.push((MEDIA_SOURCE, IppValue::Keyword(source.clone())));
+        }
+        if let Some(ref media_type) = self.media_type {
+            members.push((MEDIA_TYPE, IppValue::Keyword(media_type.clone())));
+        }
+        let margins = [
+            (MEDIA_TOP_MARGIN, self.top_margin),
+            (MEDIA_BOTTOM_MARGIN, self.bottom_margin),
+            (MEDIA_LEFT_MARGIN, self.left_margin),
+            (MEDIA_RIGHT_MARGIN, self.right_margin),
+        ];
+        for (name, margin) in margins.iter() {
+            if let Some(margin) = margin {
+                members.push((*name, IppValue::Integer(*margin)));
+            }
+        }
+        IppValue::collection(members)
+    }
+}
+
+/// Builder to create media-col collection
+#[derive(Default)]
+pub struct MediaColBuilder {
+    media: MediaCol,
+}
+
+impl MediaColBuilder {
+    /// Create media-col builder
+    pub fn new() -> MediaColBuilder {
+        MediaColBuilder::default()
+    }
+
+    /// Specify media-size member in hundredths of millimeters
+    pub fn size(mut self, x_dimension: i32, y_dimension: i32) -> Self {
+        self.media.size = Some(MediaSize {
+            x_dimension,
+            y_dimension,
+        });
+        self
+    }
+
+    /// Specify media-source member, for example `tray-1`
+    pub fn source(mut self, source: &str) -> Self {
+        self.media.source = Some(source.to_owned());
+        self
+    }
+
+    /// Specify media-type member, for example `stationery`
+    pub fn media_type(mut self, media_type: &str) -> Self {
+        self.media.media_type = Some(media_type.to_owned());
+        self
+    }
+
+    /// Specify media-top-margin member in hundredths of millimeters
+    pub fn top_margin(mut self, margin: i32) -> Self {
+        self.media.top_margin = Some(margin);
+        self
+    }
+
+    /// Specify media-bottom-margin member in hundredths of millimeters
+    pub fn bottom_margin(mut self, margin: i32) -> Self {
+        self.media.bottom_margin = Some(margin);
+        self
+    }
+
+    /// Specify media-left-margin member in hundredths of millimeters
+    pub fn left_margin(mut self, margin: i32) -> Self {
+        self.media.left_margin = Some(margin);
+        self
+    }
+
+    /// Specify media-right-margin member in hundredths of millimeters
+    pub fn right_margin(mut self, margin: i32) -> Self {
+        self.media.right_margin = Some(margin);
+        self
+    }
+
+    /// Specify all four margins in hundredths of millimeters, 0 for borderless printing
+    pub fn margins(self, margin: i32) -> Self {
+        self.top_margin(margin)
+            .bottom_margin(margin)
+            .left_margin(margin)
+            .right_margin(margin)
+    }
+
+    /// Build media collection
+    pub fn build(self) -> MediaCol {
+        self.media
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_media_col_from_value() {
+        let value = IppValue::collection(vec![
+            (
+                MEDIA_SIZE,
+                IppValue::collection(vec![
+                    (X_DIMENSION, IppValue::Integer(21000)),
+                    (Y_DIMENSION, IppValue::Integer(29700)),
+                ]),
+            ),
+            (MEDIA_SOURCE, IppValue::Keyword("tray-1".to_owned())),
+            (MEDIA_TYPE, IppValue::Keyword("stationery".to_owned())),
+            (MEDIA_TOP_MARGIN, IppValue::Integer(300)),
+        ]);
+
+        let media = MediaCol::from_value(&value).unwrap();
+        assert_eq!(
+            media.size,
+            Some(MediaSize {
+                x_dimension: 21000,
+                y_dimension: 29700
+            })
+        );
+        assert_eq!(media.source, Some("tray-1".to_owned()));
+        assert_eq!(media.media_type, Some("stationery".to_owned()));
+        assert_eq!(media.top_margin, Some(300));
+        assert_eq!(media.left_margin, None);
+
+        assert_eq!(
+            MediaCol::from_value(&IppValue::Keyword("iso_a4_210x297mm".to_owned())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_media_col_to_value() {
+        let media = MediaColBuilder::new()
+            .size(21000, 29700)
+            .source("tray-1")
+            .margins(0)
+            .build();
+        let value = media.to_value();
+
+        assert_eq!(
+            value.member(MEDIA_SIZE).and_then(|size| size.member(X_DIMENSION)),
+            Some(IppValue::Integer(21000))
+        );
+        assert_eq!(value.member(MEDIA_SOURCE), Some(IppValue::Keyword("tray-1".to_owned())));
+        assert_eq!(value.member(MEDIA_TYPE), None);
+        assert_eq!(value.member(MEDIA_RIGHT_MARGIN), Some(IppValue::Integer(0)));
+        assert_eq!(MediaCol::from_value(&value), Some(media));
+    }
+}
@@ -2,24 +2,62 @@
 //! CUPS-specific IPP operations
 //!
 
-use crate::ipp::Operation;
+use crate::attribute::{IppAttribute, FIRST_PRINTER_NAME, LIMIT};
+use crate::ipp::{DelimiterTag, Operation};
 use crate::operation::IppOperation;
 use crate::request::IppRequestResponse;
+use crate::IppValue;
 
 /// IPP operation CUPS-Get-Printers
 #[derive(Default)]
-pub struct CupsGetPrinters;
+pub struct CupsGetPrinters {
+    first_printer_name: Option<String>,
+    limit: Option<i32>,
+}
 
 impl CupsGetPrinters {
     /// Create CUPS-Get-Printers operation
     pub fn new() -> CupsGetPrinters {
         CupsGetPrinters::default()
     }
+
+    /// Create CUPS-Get-Printers operation which returns a page of printers
+    ///
+    /// * `first_printer_name` - name of the first printer to return (first-printer-name)<br/>
+    /// * `limit` - maximum number of printers to return (limit)<br/>
+    pub fn with_range<T>(first_printer_name: Option<T>, limit: Option<i32>) -> CupsGetPrinters
+    where
+        T: AsRef<str>,
+    {
+        CupsGetPrinters {
+            first_printer_name: first_printer_name.map(|v| v.as_ref().to_string()),
+            limit,
+        }
+    }
 }
 
 impl IppOperation for CupsGetPrinters {
     fn into_ipp_request(self, _uri: &str) -> IppRequestResponse {
-        IppRequestResponse::new(self.version(), Operation::CupsGetPrinters, None)
+        let mut retval = IppRequestResponse::new(self.version(), Operation::CupsGetPrinters, None);
+
+        if let Some(ref first_printer_name) = self.first_printer_name {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(
+                    FIRST_PRINTER_NAME,
+                    IppValue::NameWithoutLanguage(first_printer_name.clone()),
+                ),
+            );
+        }
+
+        if let Some(limit) = self.limit {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(LIMIT, IppValue::Integer(limit)),
+            );
+        }
+
+        retval
     }
 }
 
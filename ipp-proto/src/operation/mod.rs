@@ -1,6 +1,8 @@
 //!
 //! High-level IPP operation abstractions
 //!
+use std::io::Write;
+
 use crate::{attribute::*, ipp::*, request::IppRequestResponse, IppJobSource, IppValue};
 
 pub mod cups;
@@ -14,14 +16,107 @@ pub trait IppOperation {
     fn version(&self) -> IppVersion {
         IppVersion::Ipp11
     }
+
+    /// Return attributes-natural-language which overrides the client default for this operation, if any
+    fn natural_language(&self) -> Option<&str> {
+        None
+    }
 }
 
-/// IPP operation Print-Job
-pub struct PrintJob {
-    source: IppJobSource,
+/// Wrapper which overrides attributes-natural-language of the inner operation
+pub struct NaturalLanguageOverride<T> {
+    operation: T,
+    natural_language: Option<String>,
+}
+
+impl<T: IppOperation> NaturalLanguageOverride<T> {
+    /// Create wrapper for a given operation, `None` leaves the operation unchanged
+    pub fn new(operation: T, natural_language: Option<String>) -> NaturalLanguageOverride<T> {
+        NaturalLanguageOverride {
+            operation,
+            natural_language,
+        }
+    }
+}
+
+impl<T: IppOperation> IppOperation for NaturalLanguageOverride<T> {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = self.operation.into_ipp_request(uri);
+
+        if let Some(natural_language) = self.natural_language {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(ATTRIBUTES_NATURAL_LANGUAGE, IppValue::NaturalLanguage(natural_language)),
+            );
+        }
+
+        retval
+    }
+
+    fn version(&self) -> IppVersion {
+        self.operation.version()
+    }
+
+    fn natural_language(&self) -> Option<&str> {
+        self.natural_language
+            .as_deref()
+            .or_else(|| self.operation.natural_language())
+    }
+}
+
+// Requesting user, job name and extra attributes shared by Print-Job, Print-URI and Validate-Job
+struct JobAttributes {
     user_name: Option<String>,
     job_name: Option<String>,
     attributes: Vec<IppAttribute>,
+    operation_attributes: Vec<IppAttribute>,
+}
+
+impl JobAttributes {
+    fn new<U, N>(user_name: Option<U>, job_name: Option<N>) -> JobAttributes
+    where
+        U: AsRef<str>,
+        N: AsRef<str>,
+    {
+        JobAttributes {
+            user_name: user_name.map(|v| v.as_ref().to_string()),
+            job_name: job_name.map(|v| v.as_ref().to_string()),
+            attributes: Vec::new(),
+            operation_attributes: Vec::new(),
+        }
+    }
+
+    fn add_to(self, request: &mut IppRequestResponse) {
+        if let Some(user_name) = self.user_name {
+            request.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(REQUESTING_USER_NAME, IppValue::NameWithoutLanguage(user_name)),
+            );
+        }
+
+        if let Some(job_name) = self.job_name {
+            request.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(JOB_NAME, IppValue::NameWithoutLanguage(job_name)),
+            )
+        }
+
+        for attr in self.operation_attributes {
+            request.attributes_mut().add(DelimiterTag::OperationAttributes, attr);
+        }
+
+        for attr in self.attributes {
+            request.attributes_mut().add(DelimiterTag::JobAttributes, attr);
+        }
+    }
+}
+
+/// IPP operation Print-Job
+pub struct PrintJob {
+    source: IppJobSource,
+    job: JobAttributes,
+    tee: Option<Box<dyn Write + Send>>,
+    detect_format: bool,
 }
 
 impl PrintJob {
@@ -37,15 +132,30 @@ impl PrintJob {
     {
         PrintJob {
             source,
-            user_name: user_name.map(|v| v.as_ref().to_string()),
-            job_name: job_name.map(|v| v.as_ref().to_string()),
-            attributes: Vec::new(),
+            job: JobAttributes::new(user_name, job_name),
+            tee: None,
+            detect_format: false,
         }
     }
 
     /// Set extra job attribute for this operation, for example `colormodel=grayscale`
     pub fn add_attribute(&mut self, attribute: IppAttribute) {
-        self.attributes.push(attribute);
+        self.job.attributes.push(attribute);
+    }
+
+    /// Set extra operation attribute for this operation, for example `document-format`
+    pub fn add_operation_attribute(&mut self, attribute: IppAttribute) {
+        self.job.operation_attributes.push(attribute);
+    }
+
+    /// Write a copy of the request stream including the document to the given writer
+    pub fn set_tee(&mut self, writer: Box<dyn Write + Send>) {
+        self.tee = Some(writer);
+    }
+
+    /// Detect document-format from the document data if it is not set explicitly
+    pub fn set_detect_format(&mut self, detect: bool) {
+        self.detect_format = detect;
     }
 }
 
@@ -53,24 +163,99 @@ impl IppOperation for PrintJob {
     fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
         let mut retval = IppRequestResponse::new(self.version(), Operation::PrintJob, Some(uri));
 
-        if let Some(ref user_name) = self.user_name {
-            retval.attributes_mut().add(
-                DelimiterTag::OperationAttributes,
-                IppAttribute::new(REQUESTING_USER_NAME, IppValue::NameWithoutLanguage(user_name.clone())),
-            );
+        self.job.add_to(&mut retval);
+        retval.add_payload(self.source);
+        retval.set_detect_format(self.detect_format);
+        if let Some(tee) = self.tee {
+            retval.tee_to(tee);
         }
+        retval
+    }
+}
 
-        if let Some(ref job_name) = self.job_name {
-            retval.attributes_mut().add(
-                DelimiterTag::OperationAttributes,
-                IppAttribute::new(JOB_NAME, IppValue::NameWithoutLanguage(job_name.clone())),
-            )
+/// IPP operation Print-URI
+pub struct PrintUri {
+    document_uri: String,
+    job: JobAttributes,
+}
+
+impl PrintUri {
+    /// Create Print-URI operation
+    ///
+    /// * `document_uri` - URI of the document which the printer fetches and prints (document-uri)<br/>
+    /// * `user_name` - name of the user (requesting-user-name)<br/>
+    /// * `job_name` - job name (job-name)<br/>
+    pub fn new<U, N>(document_uri: &str, user_name: Option<U>, job_name: Option<N>) -> PrintUri
+    where
+        U: AsRef<str>,
+        N: AsRef<str>,
+    {
+        PrintUri {
+            document_uri: document_uri.to_owned(),
+            job: JobAttributes::new(user_name, job_name),
         }
+    }
 
-        for attr in &self.attributes {
-            retval.attributes_mut().add(DelimiterTag::JobAttributes, attr.clone());
+    /// Set extra job attribute for this operation, for example `colormodel=grayscale`
+    pub fn add_attribute(&mut self, attribute: IppAttribute) {
+        self.job.attributes.push(attribute);
+    }
+
+    /// Set extra operation attribute for this operation, for example `document-format`
+    pub fn add_operation_attribute(&mut self, attribute: IppAttribute) {
+        self.job.operation_attributes.push(attribute);
+    }
+}
+
+impl IppOperation for PrintUri {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::PrintUri, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(DOCUMENT_URI, IppValue::Uri(self.document_uri)),
+        );
+        self.job.add_to(&mut retval);
+        retval
+    }
+}
+
+/// IPP operation Validate-Job
+pub struct ValidateJob {
+    job: JobAttributes,
+}
+
+impl ValidateJob {
+    /// Create Validate-Job operation
+    ///
+    /// * `user_name` - name of the user (requesting-user-name)<br/>
+    /// * `job_name` - job name (job-name)<br/>
+    pub fn new<U, N>(user_name: Option<U>, job_name: Option<N>) -> ValidateJob
+    where
+        U: AsRef<str>,
+        N: AsRef<str>,
+    {
+        ValidateJob {
+            job: JobAttributes::new(user_name, job_name),
         }
-        retval.add_payload(self.source);
+    }
+
+    /// Set extra job attribute for this operation, for example `colormodel=grayscale`
+    pub fn add_attribute(&mut self, attribute: IppAttribute) {
+        self.job.attributes.push(attribute);
+    }
+
+    /// Set extra operation attribute for this operation, for example `document-format`
+    pub fn add_operation_attribute(&mut self, attribute: IppAttribute) {
+        self.job.operation_attributes.push(attribute);
+    }
+}
+
+impl IppOperation for ValidateJob {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::ValidateJob, Some(uri));
+
+        self.job.add_to(&mut retval);
         retval
     }
 }
@@ -165,6 +350,7 @@ pub struct SendDocument {
     source: IppJobSource,
     user_name: Option<String>,
     last: bool,
+    operation_attributes: Vec<IppAttribute>,
 }
 
 impl SendDocument {
@@ -183,8 +369,14 @@ impl SendDocument {
             source,
             user_name: user_name.map(|v| v.as_ref().to_string()),
             last,
+            operation_attributes: Vec::new(),
         }
     }
+
+    /// Set extra operation attribute for this operation, for example `document-format`
+    pub fn add_operation_attribute(&mut self, attribute: IppAttribute) {
+        self.operation_attributes.push(attribute);
+    }
 }
 
 impl IppOperation for SendDocument {
@@ -208,8 +400,383 @@ impl IppOperation for SendDocument {
             IppAttribute::new(LAST_DOCUMENT, IppValue::Boolean(self.last)),
         );
 
+        for attr in self.operation_attributes {
+            retval.attributes_mut().add(DelimiterTag::OperationAttributes, attr);
+        }
+
         retval.add_payload(self.source);
 
         retval
     }
 }
+
+/// IPP operation Set-Printer-Attributes
+#[derive(Default)]
+pub struct SetPrinterAttributes {
+    attributes: Vec<IppAttribute>,
+}
+
+impl SetPrinterAttributes {
+    /// Create Set-Printer-Attributes operation
+    pub fn new() -> SetPrinterAttributes {
+        SetPrinterAttributes::default()
+    }
+
+    /// Set printer attribute to modify. Use `IppValue::DeleteAttribute` to delete the attribute
+    /// or `IppValue::AdminDefine` to reset it to the administratively defined value
+    pub fn add_attribute(&mut self, attribute: IppAttribute) {
+        self.attributes.push(attribute);
+    }
+}
+
+impl IppOperation for SetPrinterAttributes {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::SetPrinterAttributes, Some(uri));
+
+        for attr in &self.attributes {
+            retval
+                .attributes_mut()
+                .add(DelimiterTag::PrinterAttributes, attr.clone());
+        }
+        retval
+    }
+}
+
+/// IPP operation Set-Job-Attributes
+pub struct SetJobAttributes {
+    job_id: i32,
+    attributes: Vec<IppAttribute>,
+}
+
+impl SetJobAttributes {
+    /// Create Set-Job-Attributes operation
+    ///
+    /// * `job_id` - id of the job to modify<br/>
+    pub fn new(job_id: i32) -> SetJobAttributes {
+        SetJobAttributes {
+            job_id,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Set job attribute to modify. Use `IppValue::DeleteAttribute` to delete the attribute
+    /// or `IppValue::AdminDefine` to reset it to the administratively defined value
+    pub fn add_attribute(&mut self, attribute: IppAttribute) {
+        self.attributes.push(attribute);
+    }
+}
+
+impl IppOperation for SetJobAttributes {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::SetJobAttributes, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(JOB_ID, IppValue::Integer(self.job_id)),
+        );
+
+        for attr in &self.attributes {
+            retval.attributes_mut().add(DelimiterTag::JobAttributes, attr.clone());
+        }
+        retval
+    }
+}
+
+/// IPP operation Get-Job-Attributes
+pub struct GetJobAttributes {
+    job_id: i32,
+    attributes: Vec<String>,
+}
+
+impl GetJobAttributes {
+    /// Create Get-Job-Attributes operation
+    ///
+    /// * `job_id` - id of the job to query<br/>
+    pub fn new(job_id: i32) -> GetJobAttributes {
+        GetJobAttributes {
+            job_id,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Set attributes to request for the job
+    pub fn with_attributes<T>(job_id: i32, attributes: &[T]) -> GetJobAttributes
+    where
+        T: AsRef<str>,
+    {
+        GetJobAttributes {
+            job_id,
+            attributes: attributes.iter().map(|a| a.as_ref().to_string()).collect(),
+        }
+    }
+}
+
+impl IppOperation for GetJobAttributes {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::GetJobAttributes, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(JOB_ID, IppValue::Integer(self.job_id)),
+        );
+
+        if !self.attributes.is_empty() {
+            let vals: Vec<IppValue> = self.attributes.iter().map(|a| IppValue::Keyword(a.clone())).collect();
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(REQUESTED_ATTRIBUTES, IppValue::ListOf(vals)),
+            );
+        }
+        retval
+    }
+}
+
+/// IPP operation Restart-Job
+pub struct RestartJob {
+    job_id: i32,
+}
+
+impl RestartJob {
+    /// Create Restart-Job operation
+    ///
+    /// * `job_id` - id of the job to restart<br/>
+    pub fn new(job_id: i32) -> RestartJob {
+        RestartJob { job_id }
+    }
+}
+
+impl IppOperation for RestartJob {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::RestartJob, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(JOB_ID, IppValue::Integer(self.job_id)),
+        );
+        retval
+    }
+}
+
+/// IPP operation Cancel-Job
+pub struct CancelJob {
+    job_id: i32,
+}
+
+impl CancelJob {
+    /// Create Cancel-Job operation
+    ///
+    /// * `job_id` - id of the job to cancel<br/>
+    pub fn new(job_id: i32) -> CancelJob {
+        CancelJob { job_id }
+    }
+}
+
+impl IppOperation for CancelJob {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::CancelJob, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(JOB_ID, IppValue::Integer(self.job_id)),
+        );
+        retval
+    }
+}
+
+/// IPP operation Get-Jobs
+pub struct GetJobs {
+    which_jobs: Option<String>,
+    my_jobs: Option<bool>,
+    limit: Option<i32>,
+    attributes: Vec<String>,
+}
+
+impl GetJobs {
+    /// Create Get-Jobs operation
+    ///
+    /// * `which_jobs` - `completed` or `not-completed`, the printer default is `not-completed`<br/>
+    pub fn new(which_jobs: Option<&str>) -> GetJobs {
+        GetJobs {
+            which_jobs: which_jobs.map(|w| w.to_owned()),
+            my_jobs: None,
+            limit: None,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Set attributes to request for each job
+    pub fn with_attributes<T>(which_jobs: Option<&str>, attributes: &[T]) -> GetJobs
+    where
+        T: AsRef<str>,
+    {
+        GetJobs {
+            which_jobs: which_jobs.map(|w| w.to_owned()),
+            my_jobs: None,
+            limit: None,
+            attributes: attributes.iter().map(|a| a.as_ref().to_string()).collect(),
+        }
+    }
+
+    /// Return only the jobs submitted by the requesting user
+    pub fn set_my_jobs(&mut self, my_jobs: bool) {
+        self.my_jobs = Some(my_jobs);
+    }
+
+    /// Set the maximum number of jobs to return
+    pub fn set_limit(&mut self, limit: i32) {
+        self.limit = Some(limit);
+    }
+}
+
+impl IppOperation for GetJobs {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::GetJobs, Some(uri));
+
+        if let Some(which_jobs) = self.which_jobs {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(WHICH_JOBS, IppValue::Keyword(which_jobs)),
+            );
+        }
+
+        if let Some(my_jobs) = self.my_jobs {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(MY_JOBS, IppValue::Boolean(my_jobs)),
+            );
+        }
+
+        if let Some(limit) = self.limit {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(LIMIT, IppValue::Integer(limit)),
+            );
+        }
+
+        if !self.attributes.is_empty() {
+            let vals: Vec<IppValue> = self.attributes.iter().map(|a| IppValue::Keyword(a.clone())).collect();
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(REQUESTED_ATTRIBUTES, IppValue::ListOf(vals)),
+            );
+        }
+        retval
+    }
+}
+
+/// IPP operation Purge-Jobs
+#[derive(Default)]
+pub struct PurgeJobs;
+
+impl PurgeJobs {
+    /// Create Purge-Jobs operation
+    pub fn new() -> PurgeJobs {
+        PurgeJobs
+    }
+}
+
+impl IppOperation for PurgeJobs {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        IppRequestResponse::new(self.version(), Operation::PurgeJobs, Some(uri))
+    }
+}
+
+/// IPP operation Hold-New-Jobs (RFC 3998). New jobs are accepted but held until released
+#[derive(Default)]
+pub struct HoldNewJobs;
+
+impl HoldNewJobs {
+    /// Create Hold-New-Jobs operation
+    pub fn new() -> HoldNewJobs {
+        HoldNewJobs
+    }
+}
+
+impl IppOperation for HoldNewJobs {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        IppRequestResponse::new(self.version(), Operation::HoldNewJobs, Some(uri))
+    }
+}
+
+/// IPP operation Release-Held-New-Jobs (RFC 3998). Releases the jobs held after Hold-New-Jobs
+#[derive(Default)]
+pub struct ReleaseHeldNewJobs;
+
+impl ReleaseHeldNewJobs {
+    /// Create Release-Held-New-Jobs operation
+    pub fn new() -> ReleaseHeldNewJobs {
+        ReleaseHeldNewJobs
+    }
+}
+
+impl IppOperation for ReleaseHeldNewJobs {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        IppRequestResponse::new(self.version(), Operation::ReleaseHeldNewJobs, Some(uri))
+    }
+}
+
+/// IPP operation Pause-Printer. The printer stops processing jobs and goes to the stopped state
+pub struct PausePrinter {
+    user_name: Option<String>,
+}
+
+impl PausePrinter {
+    /// Create Pause-Printer operation
+    ///
+    /// * `user_name` - name of the user (requesting-user-name)<br/>
+    pub fn new<U>(user_name: Option<U>) -> PausePrinter
+    where
+        U: AsRef<str>,
+    {
+        PausePrinter {
+            user_name: user_name.map(|v| v.as_ref().to_string()),
+        }
+    }
+}
+
+impl IppOperation for PausePrinter {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::PausePrinter, Some(uri));
+
+        if let Some(user_name) = self.user_name {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(REQUESTING_USER_NAME, IppValue::NameWithoutLanguage(user_name)),
+            );
+        }
+        retval
+    }
+}
+
+/// IPP operation Resume-Printer. The printer resumes processing of the paused queue
+pub struct ResumePrinter {
+    user_name: Option<String>,
+}
+
+impl ResumePrinter {
+    /// Create Resume-Printer operation
+    ///
+    /// * `user_name` - name of the user (requesting-user-name)<br/>
+    pub fn new<U>(user_name: Option<U>) -> ResumePrinter
+    where
+        U: AsRef<str>,
+    {
+        ResumePrinter {
+            user_name: user_name.map(|v| v.as_ref().to_string()),
+        }
+    }
+}
+
+impl IppOperation for ResumePrinter {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::ResumePrinter, Some(uri));
+
+        if let Some(user_name) = self.user_name {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(REQUESTING_USER_NAME, IppValue::NameWithoutLanguage(user_name)),
+            );
+        }
+        retval
+    }
+}
@@ -3,7 +3,8 @@
 //!
 use std::{
     fmt,
-    io::{self, Read},
+    io::{self, Read, Write},
+    mem,
 };
 
 use byteorder::{BigEndian, ReadBytesExt};
@@ -19,6 +20,8 @@ pub enum ParseError {
     InvalidTag(u8),
     InvalidVersion,
     InvalidCollection,
+    InvalidValue(String),
+    TooManyGroups(usize),
     Incomplete,
     IOError(io::Error),
 }
@@ -38,6 +41,8 @@ impl fmt::Display for ParseError {
             ParseError::InvalidTag(tag) => write!(f, "Invalid tag: {}", tag),
             ParseError::InvalidVersion => write!(f, "Invalid IPP protocol version"),
             ParseError::InvalidCollection => write!(f, "Invalid IPP collection"),
+            ParseError::InvalidValue(ref value) => write!(f, "Invalid value: {}", value),
+            ParseError::TooManyGroups(max) => write!(f, "Too many attribute groups, maximum is {}", max),
             ParseError::Incomplete => write!(f, "Incomplete IPP payload"),
             ParseError::IOError(err) => write!(f, "{}", err.to_string()),
         }
@@ -72,35 +77,45 @@ impl IppParseResult {
     }
 }
 
-/// IPP parser implementation
-pub struct IppParser<'a> {
-    reader: &'a mut dyn Read,
+type AttributeHandler = Box<dyn FnMut(DelimiterTag, IppAttribute) + Send>;
+
+// Parser state which is kept between the tags, so that parsing can be resumed when more data arrives
+struct ParserState {
     current_group: Option<IppAttributeGroup>,
     last_name: Option<String>,
     context: Vec<Vec<IppValue>>,
     attributes: IppAttributes,
+    groups: usize,
+    max_groups: usize,
+    lenient: bool,
+    handler: Option<AttributeHandler>,
 }
 
-impl<'a> IppParser<'a> {
-    /// Create IPP parser using the given Read
-    pub fn new(reader: &'a mut dyn Read) -> IppParser<'a> {
-        IppParser {
-            reader,
+impl ParserState {
+    fn new() -> ParserState {
+        ParserState {
             current_group: None,
             last_name: None,
             context: vec![vec![]],
             attributes: IppAttributes::new(),
+            groups: 0,
+            max_groups: IppParser::DEFAULT_MAX_GROUPS,
+            lenient: false,
+            handler: None,
         }
     }
 
     fn add_last_attribute(&mut self) {
-        if let Some(ref last_name) = self.last_name {
+        if let Some(last_name) = self.last_name.take() {
             if let Some(val_list) = self.context.pop() {
                 if let Some(ref mut group) = self.current_group {
-                    group.attributes_mut().insert(
-                        last_name.clone(),
-                        IppAttribute::new(&last_name, list_or_value(val_list)),
-                    );
+                    let attribute = IppAttribute::new(&last_name, list_or_value(val_list));
+                    match self.handler {
+                        Some(ref mut handler) => handler(group.tag(), attribute),
+                        None => {
+                            group.attributes_mut().insert(last_name, attribute);
+                        }
+                    }
                 }
             }
             self.context.push(vec![]);
@@ -108,15 +123,23 @@ impl<'a> IppParser<'a> {
     }
 
     fn parse_delimiter(&mut self, tag: u8) -> Result<DelimiterTag, ParseError> {
-        debug!("Delimiter tag: {:0x}", tag);
-
         let tag = DelimiterTag::from_u8(tag).ok_or_else(|| ParseError::InvalidTag(tag))?;
-        if tag == DelimiterTag::EndOfAttributes {
-            self.add_last_attribute();
-        }
+        debug!("Delimiter tag: {}", tag);
+
+        // last attribute belongs to the previous group
+        self.add_last_attribute();
 
         if let Some(group) = self.current_group.take() {
-            self.attributes.groups_mut().push(group);
+            self.groups += 1;
+            // with the handler the attributes are not collected
+            if self.handler.is_none() {
+                self.attributes.groups_mut().push(group);
+            }
+        }
+
+        if tag != DelimiterTag::EndOfAttributes && self.groups >= self.max_groups {
+            error!("Too many attribute groups");
+            return Err(ParseError::TooManyGroups(self.max_groups));
         }
 
         self.current_group = Some(IppAttributeGroup::new(tag));
@@ -124,14 +147,19 @@ impl<'a> IppParser<'a> {
         Ok(tag)
     }
 
-    fn parse_value(&mut self, tag: u8) -> Result<(), ParseError> {
+    fn parse_value(&mut self, tag: u8, reader: &mut dyn Read) -> Result<(), ParseError> {
         // value tag
-        let namelen = self.reader.read_u16::<BigEndian>()?;
-        let name = self.reader.read_string(namelen as usize)?;
-        let value = IppValue::read(tag, &mut self.reader)?;
+        let namelen = reader.read_u16::<BigEndian>()?;
+        let name = reader.read_string(namelen as usize)?;
+        let value = IppValue::read(tag, reader)?;
 
         debug!("Value tag: {:0x}: {}: {}", tag, name, value);
 
+        if self.current_group.is_none() && self.lenient {
+            debug!("Value before the first delimiter, adding implicit operation attributes group");
+            self.current_group = Some(IppAttributeGroup::new(DelimiterTag::OperationAttributes));
+        }
+
         if namelen > 0 {
             // single attribute or begin of array
             self.add_last_attribute();
@@ -171,40 +199,151 @@ impl<'a> IppParser<'a> {
         Ok(())
     }
 
+    // Parse next tag from the reader, returns true when the end of attributes is reached.
+    // All data for the tag is read before the state is modified.
+    fn parse_tag(&mut self, reader: &mut dyn Read) -> Result<bool, ParseError> {
+        match reader.read_u8()? {
+            tag @ 0x01...0x05 => Ok(self.parse_delimiter(tag)? == DelimiterTag::EndOfAttributes),
+            tag @ 0x10...0x4a => {
+                self.parse_value(tag, reader)?;
+                Ok(false)
+            }
+            tag => Err(ParseError::InvalidTag(tag)),
+        }
+    }
+}
+
+/// IPP parser implementation
+pub struct IppParser<'a> {
+    reader: &'a mut dyn Read,
+    state: ParserState,
+}
+
+impl<'a> IppParser<'a> {
+    /// Default maximum number of attribute groups
+    pub const DEFAULT_MAX_GROUPS: usize = 100_000;
+
+    /// Create IPP parser using the given Read
+    pub fn new(reader: &'a mut dyn Read) -> IppParser<'a> {
+        IppParser {
+            reader,
+            state: ParserState::new(),
+        }
+    }
+
+    /// Enable or disable lenient parsing. In lenient mode the attributes which appear before
+    /// the first delimiter tag are added to an implicit operation attributes group instead of being dropped
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.state.lenient = lenient;
+        self
+    }
+
+    /// Set maximum number of attribute groups, parsing fails with `ParseError::TooManyGroups` if exceeded
+    pub fn with_max_groups(mut self, max_groups: usize) -> Self {
+        self.state.max_groups = max_groups;
+        self
+    }
+
+    /// Pass every parsed attribute together with its group tag to the given handler.
+    /// The attributes are not collected in the parse result in this case.
+    pub fn with_handler<F>(mut self, handler: F) -> Self
+    where
+        F: FnMut(DelimiterTag, IppAttribute) + Send + 'static,
+    {
+        self.state.handler = Some(Box::new(handler));
+        self
+    }
+
     /// Parse IPP stream
     pub fn parse(mut self) -> Result<IppParseResult, ParseError> {
         let header = IppHeader::from_reader(self.reader)?;
         debug!("IPP header: {:?}", header);
 
+        while !self.state.parse_tag(self.reader)? {}
+
+        Ok(IppParseResult::new(header, self.state.attributes))
+    }
+}
+
+// Incremental parser for the data which arrives in chunks of arbitrary size.
+// Only complete tags are consumed from the buffer, the incomplete tail is kept until more data arrives,
+// so a chunk boundary in the middle of a length field or value is handled without reparsing.
+struct BufferedParser {
+    buffer: Vec<u8>,
+    header: Option<IppHeader>,
+    state: ParserState,
+}
+
+impl BufferedParser {
+    fn new(state: ParserState) -> BufferedParser {
+        BufferedParser {
+            buffer: Vec::new(),
+            header: None,
+            state,
+        }
+    }
+
+    // Append data and parse all complete tags. Returns true when the end of attributes is reached,
+    // in which case the buffer holds the data which follows the attributes.
+    fn feed(&mut self, data: &[u8]) -> Result<bool, ParseError> {
+        self.buffer.extend_from_slice(data);
+
+        let mut reader = io::Cursor::new(&self.buffer[..]);
+        let mut consumed = 0;
+        let mut finished = false;
+
         loop {
-            match self.reader.read_u8()? {
-                tag @ 0x01...0x05 => {
-                    if self.parse_delimiter(tag)? == DelimiterTag::EndOfAttributes {
-                        break;
+            let result = if self.header.is_some() {
+                self.state.parse_tag(&mut reader)
+            } else {
+                match IppHeader::from_reader(&mut reader) {
+                    Ok(header) => {
+                        debug!("IPP header: {:?}", header);
+                        self.header = Some(header);
+                        Ok(false)
                     }
+                    Err(e) => Err(e),
                 }
-                tag @ 0x10...0x4a => self.parse_value(tag)?,
-                tag => {
-                    return Err(ParseError::InvalidTag(tag));
+            };
+            match result {
+                Ok(end) => {
+                    consumed = reader.position() as usize;
+                    if end {
+                        finished = true;
+                        break;
+                    }
                 }
+                Err(ParseError::Incomplete) => break,
+                Err(e) => return Err(e),
             }
         }
 
-        Ok(IppParseResult::new(header, self.attributes))
+        self.buffer.drain(..consumed);
+        Ok(finished)
     }
 }
 
-enum AsyncParseState {
-    Headers(Vec<u8>),
-    Payload(IppParseResult),
-}
-
 /// Asynchronous IPP parser using Streams
 pub struct AsyncIppParser<I, E> {
-    state: AsyncParseState,
+    parser: BufferedParser,
+    result: Option<IppParseResult>,
     stream: Box<dyn Stream<Item = I, Error = E> + Send>,
 }
 
+impl<I, E> AsyncIppParser<I, E> {
+    /// Set maximum number of attribute groups, parsing fails with `ParseError::TooManyGroups` if exceeded
+    pub fn with_max_groups(mut self, max_groups: usize) -> Self {
+        self.parser.state.max_groups = max_groups;
+        self
+    }
+
+    /// Enable or disable lenient parsing, see `IppParser::with_lenient`
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.parser.state.lenient = lenient;
+        self
+    }
+}
+
 impl<I, E> Future for AsyncIppParser<I, E>
 where
     I: AsRef<[u8]>,
@@ -215,34 +354,26 @@ where
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
         while let Some(item) = try_ready!(self.stream.poll()) {
-            match self.state {
-                AsyncParseState::Headers(ref mut buffer) => {
-                    buffer.extend_from_slice(item.as_ref());
-                    let length = buffer.len() as u64;
-
-                    let mut reader = io::Cursor::new(buffer);
-                    let parser = IppParser::new(&mut reader);
-
-                    match parser.parse() {
-                        Ok(mut result) => {
-                            debug!("Parse ok, proceeding to payload state");
-                            if reader.position() < length {
-                                debug!("Adding residual payload from this chunk");
-                                let mut temp = tempfile::NamedTempFile::new()?;
-                                io::copy(&mut reader, &mut temp)?;
-                                result.payload = Some(PayloadKind::ReceivedData(temp));
-                            }
-                            self.state = AsyncParseState::Payload(result);
-                        }
-                        Err(ParseError::Incomplete) => {
-                            debug!("Incomplete request, awaiting for more data");
-                        }
-                        Err(e) => {
-                            return Err(e);
-                        }
+            match self.result {
+                None => {
+                    if !self.parser.feed(item.as_ref())? {
+                        debug!("Incomplete request, awaiting for more data");
+                        continue;
+                    }
+                    debug!("Parse ok, proceeding to payload state");
+                    let header = self.parser.header.take().ok_or(ParseError::Incomplete)?;
+                    let attributes = mem::replace(&mut self.parser.state.attributes, IppAttributes::new());
+                    let mut result = IppParseResult::new(header, attributes);
+                    if !self.parser.buffer.is_empty() {
+                        debug!("Adding residual payload from this chunk");
+                        let mut temp = tempfile::NamedTempFile::new()?;
+                        temp.write_all(&self.parser.buffer)?;
+                        self.parser.buffer.clear();
+                        result.payload = Some(PayloadKind::ReceivedData(temp));
                     }
+                    self.result = Some(result);
                 }
-                AsyncParseState::Payload(ref mut result) => {
+                Some(ref mut result) => {
                     let mut reader = io::Cursor::new(&item);
                     match result.payload {
                         Some(PayloadKind::ReceivedData(ref mut file)) => {
@@ -264,15 +395,11 @@ where
             }
         }
 
-        match self.state {
-            AsyncParseState::Headers(_) => Err(ParseError::Incomplete),
-            AsyncParseState::Payload(ref mut result) => {
+        match self.result.take() {
+            None => Err(ParseError::Incomplete),
+            Some(result) => {
                 debug!("Parsing finished, payload: {}", result.payload.is_some());
-                Ok(Async::Ready(IppParseResult {
-                    header: result.header.clone(),
-                    attributes: result.attributes.clone(),
-                    payload: result.payload.take(),
-                }))
+                Ok(Async::Ready(result))
             }
         }
     }
@@ -282,16 +409,75 @@ impl<I, E> From<Box<dyn Stream<Item = I, Error = E> + Send>> for AsyncIppParser<
     /// Construct asynchronous parser from the stream
     fn from(s: Box<dyn Stream<Item = I, Error = E> + Send>) -> AsyncIppParser<I, E> {
         AsyncIppParser {
-            state: AsyncParseState::Headers(Vec::new()),
+            parser: BufferedParser::new(ParserState::new()),
+            result: None,
             stream: s,
         }
     }
 }
 
+/// Asynchronous IPP parser which passes every attribute to a handler as soon as it is decoded,
+/// without collecting the attributes in memory. Resolves to the IPP header of the response.
+/// Any data following the end of attributes is ignored.
+pub struct AsyncIppStreamParser<I, E> {
+    stream: Box<dyn Stream<Item = I, Error = E> + Send>,
+    parser: BufferedParser,
+}
+
+impl<I, E> AsyncIppStreamParser<I, E> {
+    /// Create streaming parser with a given attribute handler
+    pub fn new<F>(stream: Box<dyn Stream<Item = I, Error = E> + Send>, handler: F) -> AsyncIppStreamParser<I, E>
+    where
+        F: FnMut(DelimiterTag, IppAttribute) + Send + 'static,
+    {
+        let mut state = ParserState::new();
+        state.handler = Some(Box::new(handler));
+        AsyncIppStreamParser {
+            stream,
+            parser: BufferedParser::new(state),
+        }
+    }
+
+    /// Set maximum number of attribute groups, parsing fails with `ParseError::TooManyGroups` if exceeded
+    pub fn with_max_groups(mut self, max_groups: usize) -> Self {
+        self.parser.state.max_groups = max_groups;
+        self
+    }
+
+    /// Enable or disable lenient parsing, see `IppParser::with_lenient`
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.parser.state.lenient = lenient;
+        self
+    }
+}
+
+impl<I, E> Future for AsyncIppStreamParser<I, E>
+where
+    I: AsRef<[u8]>,
+    ParseError: From<E>,
+{
+    type Item = IppHeader;
+    type Error = ParseError;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        while let Some(item) = try_ready!(self.stream.poll()) {
+            if self.parser.feed(item.as_ref())? {
+                debug!("Parsing finished");
+                if let Some(header) = self.parser.header.take() {
+                    return Ok(Async::Ready(header));
+                }
+            }
+        }
+        Err(ParseError::Incomplete)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
 
+    use crate::attribute::ATTRIBUTES_CHARSET;
+
     use super::*;
 
     #[test]
@@ -357,6 +543,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_too_many_groups() {
+        let data = &[1, 1, 0, 0, 0, 0, 0, 0, 4, 4, 4, 3];
+        assert!(IppParser::new(&mut Cursor::new(data))
+            .with_max_groups(3)
+            .parse()
+            .is_ok());
+
+        match IppParser::new(&mut Cursor::new(data)).with_max_groups(2).parse() {
+            Err(ParseError::TooManyGroups(2)) => {}
+            _ => panic!("Expected TooManyGroups error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        // printer attributes without the leading delimiter
+        let data = &[
+            1, 1, 0, 0, 0, 0, 0, 0, 0x21, 0x00, 0x04, b't', b'e', b's', b't', 0x00, 0x04, 0x12, 0x34, 0x56, 0x78, 4,
+            0x21, 0x00, 0x01, b'x', 0x00, 0x04, 0, 0, 0, 1, 3,
+        ];
+
+        let res = IppParser::new(&mut Cursor::new(data)).parse().unwrap();
+        assert!(res.attributes.groups_of(DelimiterTag::OperationAttributes).is_empty());
+
+        let res = IppParser::new(&mut Cursor::new(data))
+            .with_lenient(true)
+            .parse()
+            .unwrap();
+        let attrs = res.attributes.groups_of(DelimiterTag::OperationAttributes)[0].attributes();
+        assert_eq!(attrs.get("test").unwrap().value().as_integer(), Some(&0x12345678));
+        let attrs = res.attributes.groups_of(DelimiterTag::PrinterAttributes)[0].attributes();
+        assert_eq!(attrs.get("x").unwrap().value().as_integer(), Some(&1));
+    }
+
     #[test]
     fn test_async_parser_with_payload() {
         // split IPP into arbitrary chunks
@@ -393,4 +614,127 @@ mod tests {
         }
     }
 
+    // Reader which returns at most one byte per read call, like a trickle-fed connection
+    struct TrickleReader(Cursor<Vec<u8>>);
+
+    impl Read for TrickleReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = std::cmp::min(buf.len(), 1);
+            self.0.read(&mut buf[..len])
+        }
+    }
+
+    fn trickle_data() -> Vec<u8> {
+        vec![
+            1, 1, 0, 0, 0, 0, 0, 7, 1, 0x47, 0, 18, b'a', b't', b't', b'r', b'i', b'b', b'u', b't', b'e', b's', b'-',
+            b'c', b'h', b'a', b'r', b's', b'e', b't', 0, 5, b'u', b't', b'f', b'-', b'8', 4, 0x34, 0, 4, b'c', b'o',
+            b'l', b'l', 0, 0, 0x4a, 0, 0, 0, 1, b'x', 0x21, 0, 0, 0, 4, 0x12, 0x34, 0x56, 0x78, 0x37, 0, 0, 0, 0, 0x21,
+            0, 4, b't', b'e', b's', b't', 0, 4, 0, 0, 0, 1, 0x21, 0, 0, 0, 4, 0, 0, 0, 2, 3, b'f', b'o', b'o',
+        ]
+    }
+
+    fn check_trickle_result(res: &IppParseResult) {
+        assert_eq!(res.header.request_id, 7);
+        let attrs = res.attributes.groups_of(DelimiterTag::OperationAttributes)[0].attributes();
+        assert_eq!(
+            attrs.get(ATTRIBUTES_CHARSET).unwrap().value(),
+            &IppValue::Charset("utf-8".to_owned())
+        );
+        let attrs = res.attributes.groups_of(DelimiterTag::PrinterAttributes)[0].attributes();
+        assert_eq!(
+            attrs.get("coll").unwrap().value().member("x"),
+            Some(IppValue::Integer(0x12345678))
+        );
+        assert_eq!(
+            attrs.get("test").unwrap().value(),
+            &IppValue::ListOf(vec![IppValue::Integer(1), IppValue::Integer(2)])
+        );
+    }
+
+    #[test]
+    fn test_parse_one_byte_reads() {
+        let mut reader = TrickleReader(Cursor::new(trickle_data()));
+        let res = IppParser::new(&mut reader).parse().unwrap();
+        check_trickle_result(&res);
+
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b"foo");
+    }
+
+    #[test]
+    fn test_async_parser_one_byte_chunks() {
+        let data = trickle_data().into_iter().map(|b| vec![b]).collect::<Vec<_>>();
+        let source: Box<dyn Stream<Item = Vec<u8>, Error = io::Error> + Send> =
+            Box::new(futures::stream::iter_ok::<_, io::Error>(data));
+
+        let res = AsyncIppParser::from(source).wait().unwrap();
+        check_trickle_result(&res);
+
+        match res.payload {
+            Some(PayloadKind::ReceivedData(f)) => assert_eq!(std::fs::read_to_string(f.path()).unwrap(), "foo"),
+            _ => panic!("Wrong payload!"),
+        }
+
+        // truncated at any position the parser reports incomplete data
+        for len in 0..trickle_data().len() - 3 {
+            let data = trickle_data()[..len].iter().map(|b| vec![*b]).collect::<Vec<_>>();
+            let source: Box<dyn Stream<Item = Vec<u8>, Error = io::Error> + Send> =
+                Box::new(futures::stream::iter_ok::<_, io::Error>(data));
+            match AsyncIppParser::from(source).wait() {
+                Err(ParseError::Incomplete) => {}
+                _ => panic!("Incomplete error expected at {}", len),
+            }
+        }
+    }
+
+    #[test]
+    fn test_async_stream_parser() {
+        // attribute values are split between the chunks
+        let data = vec![
+            vec![1, 1, 0, 0],
+            vec![0, 0, 0, 0, 4, 0x21, 0x00],
+            vec![0x04, b't', b'e', b's', b't', 0x00, 0x04, 0x12, 0x34],
+            vec![0x56, 0x78, 0x21, 0x00, 0x00, 0x00, 0x04, 0x77, 0x65, 0x43, 0x21, 2],
+            vec![
+                0x21, 0x00, 0x01, b'x', 0x00, 0x04, 0, 0, 0, 1, 2, 0x21, 0x00, 0x01, b'x',
+            ],
+            vec![0x00, 0x04, 0, 0, 0, 2, 3],
+        ];
+
+        let source: Box<dyn Stream<Item = Vec<u8>, Error = io::Error> + Send> =
+            Box::new(futures::stream::iter_ok::<_, io::Error>(data));
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let parser = AsyncIppStreamParser::new(source, move |tag, attr| tx.send((tag, attr)).unwrap());
+
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let header = runtime.block_on(parser).unwrap();
+        assert_eq!(header.operation_status, 0);
+
+        let attrs = rx.try_iter().collect::<Vec<_>>();
+        assert_eq!(attrs.len(), 3);
+        assert_eq!(attrs[0].0, DelimiterTag::PrinterAttributes);
+        assert_eq!(
+            attrs[0].1.value(),
+            &IppValue::ListOf(vec![IppValue::Integer(0x12345678), IppValue::Integer(0x77654321)])
+        );
+        assert_eq!(attrs[1].0, DelimiterTag::JobAttributes);
+        assert_eq!(attrs[1].1.value(), &IppValue::Integer(1));
+        assert_eq!(attrs[2].0, DelimiterTag::JobAttributes);
+        assert_eq!(attrs[2].1.value(), &IppValue::Integer(2));
+    }
+
+    #[test]
+    fn test_async_stream_parser_too_many_groups() {
+        let data = vec![vec![1, 1, 0, 0, 0, 0, 0, 0, 2, 2, 2, 3]];
+        let source: Box<dyn Stream<Item = Vec<u8>, Error = io::Error> + Send> =
+            Box::new(futures::stream::iter_ok::<_, io::Error>(data));
+
+        let parser = AsyncIppStreamParser::new(source, |_, _| {}).with_max_groups(2);
+        match parser.wait() {
+            Err(ParseError::TooManyGroups(2)) => {}
+            _ => panic!("Too many groups error expected"),
+        }
+    }
 }
@@ -0,0 +1,690 @@
+//!
+//! Printer information helpers
+//!
+use std::{collections::HashMap, time::SystemTime};
+
+use num_traits::FromPrimitive;
+
+use crate::{
+    attribute::*, ipp::*, media::MediaCol, tray::Tray, IppAttribute, IppAttributeGroup, IppAttributes, IppValue,
+};
+
+/// Printer model and firmware identification
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DeviceIdentity {
+    /// printer-make-and-model attribute
+    pub make_and_model: Option<String>,
+    /// printer-device-id attribute (IEEE 1284 device ID)
+    pub device_id: Option<String>,
+    /// printer-firmware-name attribute, one entry per firmware component
+    pub firmware_names: Vec<String>,
+    /// printer-firmware-string-version attribute, one entry per firmware component
+    pub firmware_versions: Vec<String>,
+}
+
+/// Printer information extracted from the printer attributes group
+#[derive(Clone, Debug)]
+pub struct PrinterInfo {
+    attributes: HashMap<String, IppAttribute>,
+}
+
+impl PrinterInfo {
+    /// Create printer information from the attribute group
+    pub fn from_group(group: &IppAttributeGroup) -> PrinterInfo {
+        PrinterInfo {
+            attributes: group.attributes().clone(),
+        }
+    }
+
+    /// Create a list of printers from all printer attribute groups in the response,
+    /// for example from CUPS-Get-Printers operation
+    pub fn list(attrs: &IppAttributes) -> Vec<PrinterInfo> {
+        attrs
+            .groups_of(DelimiterTag::PrinterAttributes)
+            .into_iter()
+            .map(PrinterInfo::from_group)
+            .collect()
+    }
+
+    /// Return all printer attributes
+    pub fn attributes(&self) -> &HashMap<String, IppAttribute> {
+        &self.attributes
+    }
+
+    fn str_value(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .get(name)
+            .and_then(|attr| attr.value().into_iter().next())
+            .and_then(|v| v.as_str())
+    }
+
+    fn str_values(&self, name: &str) -> Vec<String> {
+        self.attributes
+            .get(name)
+            .map(|attr| {
+                attr.value()
+                    .into_iter()
+                    .filter_map(|v| v.as_str())
+                    .map(ToOwned::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Return printer-name attribute
+    pub fn name(&self) -> Option<&str> {
+        self.str_value(PRINTER_NAME)
+    }
+
+    /// Return printer-uri-supported attribute. If the printer supports several URIs the first one is returned
+    pub fn uri(&self) -> Option<&str> {
+        self.str_value(PRINTER_URI_SUPPORTED)
+    }
+
+    /// Return device-uri attribute (CUPS-specific)
+    pub fn device_uri(&self) -> Option<&str> {
+        self.str_value(DEVICE_URI)
+    }
+
+    /// Return printer-info attribute
+    pub fn info(&self) -> Option<&str> {
+        self.str_value(PRINTER_INFO)
+    }
+
+    /// Return printer-location attribute
+    pub fn location(&self) -> Option<&str> {
+        self.str_value(PRINTER_LOCATION)
+    }
+
+    /// Return printer-make-and-model attribute
+    pub fn make_and_model(&self) -> Option<&str> {
+        self.str_value(PRINTER_MAKE_AND_MODEL)
+    }
+
+    /// Return printer model and firmware identification
+    pub fn device_identity(&self) -> DeviceIdentity {
+        DeviceIdentity {
+            make_and_model: self.make_and_model().map(ToOwned::to_owned),
+            device_id: self.str_value(PRINTER_DEVICE_ID).map(ToOwned::to_owned),
+            firmware_names: self.str_values(PRINTER_FIRMWARE_NAME),
+            firmware_versions: self.str_values(PRINTER_FIRMWARE_STRING_VERSION),
+        }
+    }
+
+    /// Return printer-state attribute
+    pub fn state(&self) -> Option<PrinterState> {
+        self.attributes
+            .get(PRINTER_STATE)
+            .and_then(|attr| attr.value().as_enum())
+            .and_then(|v| PrinterState::from_i32(*v))
+    }
+}
+
+/// Printer capabilities: the `-supported` and `-default` attributes returned by GetPrinterAttributes operation.
+///
+/// Job attributes set with the operation builders, for example finishings-col, job-sheets or
+/// job-delay-output-until-time, can be checked with `validate_job_attributes` before the job
+/// is submitted, and single values such as document-charset with `is_supported`.
+#[derive(Clone, Debug)]
+pub struct PrinterCapabilities {
+    attributes: HashMap<String, IppAttribute>,
+}
+
+impl PrinterCapabilities {
+    /// Create printer capabilities from the first printer attributes group in the response
+    pub fn from_attributes(attrs: &IppAttributes) -> PrinterCapabilities {
+        PrinterCapabilities {
+            attributes: attrs
+                .groups_of(DelimiterTag::PrinterAttributes)
+                .first()
+                .map(|g| g.attributes().clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Return all printer attributes
+    pub fn attributes(&self) -> &HashMap<String, IppAttribute> {
+        &self.attributes
+    }
+
+    /// Return the value of `<name>-supported` attribute
+    pub fn supported(&self, name: &str) -> Option<&IppValue> {
+        self.attributes
+            .get(&format!("{}-supported", name))
+            .map(|attr| attr.value())
+    }
+
+    /// Return the value of `<name>-default` attribute
+    pub fn default(&self, name: &str) -> Option<&IppValue> {
+        self.attributes
+            .get(&format!("{}-default", name))
+            .map(|attr| attr.value())
+    }
+
+    /// Check whether the printer can print in color: either color-supported is true
+    /// or print-color-mode-supported contains `color`. Some printers report only one of them
+    pub fn supports_color(&self) -> bool {
+        let color_supported = self
+            .attributes
+            .get(COLOR_SUPPORTED)
+            .and_then(|attr| attr.value().as_boolean())
+            .cloned()
+            .unwrap_or(false);
+
+        color_supported
+            || self
+                .supported(PRINT_COLOR_MODE)
+                .map(|v| {
+                    v.into_iter()
+                        .any(|v| v.as_str() == Some(PrintColorMode::Color.to_keyword()))
+                })
+                .unwrap_or(false)
+    }
+
+    /// Return pages-per-minute attribute: rated monochrome speed of the printer
+    pub fn pages_per_minute(&self) -> Option<i32> {
+        self.integer(PAGES_PER_MINUTE)
+    }
+
+    /// Return pages-per-minute-color attribute: rated color speed, reported by color printers only
+    pub fn pages_per_minute_color(&self) -> Option<i32> {
+        self.integer(PAGES_PER_MINUTE_COLOR)
+    }
+
+    fn integer(&self, name: &str) -> Option<i32> {
+        self.attributes
+            .get(name)
+            .and_then(|attr| attr.value().as_integer())
+            .cloned()
+    }
+
+    /// Return media-col-ready attribute: a list of the currently loaded media
+    pub fn media_col_ready(&self) -> Vec<MediaCol> {
+        self.attributes
+            .get(MEDIA_COL_READY)
+            .map(|attr| attr.value().into_iter().filter_map(MediaCol::from_value).collect())
+            .unwrap_or_default()
+    }
+
+    /// Return printer-input-tray attribute: a list of the input trays
+    pub fn input_trays(&self) -> Vec<Tray> {
+        self.trays(PRINTER_INPUT_TRAY)
+    }
+
+    /// Return printer-output-tray attribute: a list of the output trays
+    pub fn output_trays(&self) -> Vec<Tray> {
+        self.trays(PRINTER_OUTPUT_TRAY)
+    }
+
+    fn trays(&self, name: &str) -> Vec<Tray> {
+        self.attributes
+            .get(name)
+            .map(|attr| {
+                attr.value()
+                    .into_iter()
+                    .filter_map(|v| v.as_str())
+                    .map(Tray::parse)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Return printer-kind attribute: the kinds of media the printer is designed for,
+    /// for example `document` or `label`. Empty if the printer does not report it
+    pub fn printer_kind(&self) -> Vec<PrinterKind> {
+        self.attributes
+            .get(PRINTER_KIND)
+            .map(|attr| {
+                attr.value()
+                    .into_iter()
+                    .filter_map(|v| v.as_str())
+                    .filter_map(PrinterKind::from_keyword)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Return printer-mandatory-job-attributes attribute: names of the job attributes
+    /// which must be supplied with every job. Empty if the printer does not report it
+    pub fn mandatory_job_attributes(&self) -> Vec<String> {
+        self.attributes
+            .get(PRINTER_MANDATORY_JOB_ATTRIBUTES)
+            .map(|attr| {
+                attr.value()
+                    .into_iter()
+                    .filter_map(|v| v.as_str())
+                    .map(ToOwned::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Check whether a given value of the attribute is supported by the printer.
+    /// Attributes without the corresponding `-supported` attribute are assumed to be supported.
+    /// Collection member names are checked against the `-supported` attribute of the collection
+    /// and member values against their own `-supported` attributes.
+    pub fn is_supported(&self, name: &str, value: &IppValue) -> bool {
+        match *value {
+            IppValue::ListOf(ref list) => list.iter().all(|v| self.is_supported(name, v)),
+            IppValue::Collection(_) => self.is_collection_supported(name, value),
+            _ => match self.supported(name) {
+                Some(supported) => supported.into_iter().any(|s| is_value_supported(value, s)),
+                None => true,
+            },
+        }
+    }
+
+    /// Check whether the other printer supports at least the media, sides, color modes
+    /// and document formats supported by this printer, so that jobs can be routed to either of them.
+    /// Attributes which are missing in this printer are not compared.
+    pub fn is_compatible_with(&self, other: &PrinterCapabilities) -> bool {
+        COMPATIBILITY_ATTRIBUTES.iter().all(|name| match self.supported(name) {
+            Some(supported) => match other.supported(name) {
+                Some(other_supported) => supported
+                    .into_iter()
+                    .all(|v| other_supported.into_iter().any(|s| is_value_supported(v, s))),
+                None => false,
+            },
+            None => true,
+        })
+    }
+
+    fn is_collection_supported(&self, name: &str, value: &IppValue) -> bool {
+        let members = value.members();
+
+        let names_supported = match self.supported(name) {
+            Some(IppValue::Boolean(b)) => *b,
+            // some collections like media-size-supported list the supported values rather than member names
+            Some(supported) if supported.into_iter().all(|s| s.as_str().is_some()) => members
+                .iter()
+                .all(|(member, _)| supported.into_iter().any(|s| s.as_str() == Some(member))),
+            _ => true,
+        };
+
+        names_supported && members.iter().all(|(member, v)| self.is_supported(member, v))
+    }
+
+    /// Validate job attributes against the printer `-supported` attributes.
+    /// Returns a list of attribute names which would be unsupported by the printer.
+    pub fn validate_job_attributes(&self, attributes: &[IppAttribute]) -> Result<(), Vec<String>> {
+        let unsupported = attributes
+            .iter()
+            .filter(|attr| !self.is_supported(attr.name(), attr.value()))
+            .map(|attr| attr.name().to_owned())
+            .collect::<Vec<_>>();
+
+        if unsupported.is_empty() {
+            Ok(())
+        } else {
+            Err(unsupported)
+        }
+    }
+}
+
+const COMPATIBILITY_ATTRIBUTES: &[&str] = &[MEDIA, SIDES, PRINT_COLOR_MODE, DOCUMENT_FORMAT];
+
+fn seconds_from_now(value: &IppValue) -> Option<i64> {
+    let time = value.to_system_time()?;
+    Some(match time.duration_since(SystemTime::now()) {
+        Ok(d) => d.as_secs() as i64,
+        Err(_) => 0,
+    })
+}
+
+fn is_value_supported(value: &IppValue, supported: &IppValue) -> bool {
+    match (value, supported) {
+        (_, IppValue::Boolean(b)) => *b,
+        (IppValue::Integer(i), IppValue::RangeOfInteger { min, max }) => i >= min && i <= max,
+        // dateTime values like job-hold-until-time are supported as a range of seconds from now
+        (IppValue::DateTime { .. }, IppValue::RangeOfInteger { min, max }) => match seconds_from_now(value) {
+            Some(secs) => secs >= i64::from(*min) && secs <= i64::from(*max),
+            None => false,
+        },
+        (v, s) if v == s => true,
+        (v, s) => v.as_str().is_some() && v.as_str() == s.as_str(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{parser::IppParser, IppValue};
+
+    use super::*;
+
+    #[test]
+    fn test_printer_list() {
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 1];
+        for (name, state) in &[("first", 3u8), ("second", 5u8)] {
+            data.push(DelimiterTag::PrinterAttributes as u8);
+            data.extend(&[0x42, 0, 12]);
+            data.extend(b"printer-name");
+            data.extend(&[0, name.len() as u8]);
+            data.extend(name.as_bytes());
+            data.extend(&[0x23, 0, 13]);
+            data.extend(b"printer-state");
+            data.extend(&[0, 4, 0, 0, 0, *state]);
+        }
+        data.push(DelimiterTag::EndOfAttributes as u8);
+
+        let result = IppParser::new(&mut Cursor::new(data)).parse().unwrap();
+        let printers = PrinterInfo::list(&result.attributes);
+
+        assert_eq!(printers.len(), 2);
+        assert_eq!(printers[0].name(), Some("first"));
+        assert_eq!(printers[0].state(), Some(PrinterState::Idle));
+        assert_eq!(printers[1].name(), Some("second"));
+        assert_eq!(printers[1].state(), Some(PrinterState::Stopped));
+        assert_eq!(printers[1].location(), None);
+    }
+
+    #[test]
+    fn test_printer_uri_list() {
+        let mut group = IppAttributeGroup::new(DelimiterTag::PrinterAttributes);
+        group.attributes_mut().insert(
+            PRINTER_URI_SUPPORTED.to_owned(),
+            IppAttribute::new(
+                PRINTER_URI_SUPPORTED,
+                IppValue::ListOf(vec![
+                    IppValue::Uri("ipps://localhost/ipp/print".to_owned()),
+                    IppValue::Uri("ipp://localhost/ipp/print".to_owned()),
+                ]),
+            ),
+        );
+        let info = PrinterInfo::from_group(&group);
+        assert_eq!(info.uri(), Some("ipps://localhost/ipp/print"));
+    }
+
+    #[test]
+    fn test_device_identity() {
+        let mut group = IppAttributeGroup::new(DelimiterTag::PrinterAttributes);
+        let attrs = vec![
+            IppAttribute::new(
+                PRINTER_MAKE_AND_MODEL,
+                IppValue::TextWithoutLanguage("ACME LaserJet 9000".to_owned()),
+            ),
+            IppAttribute::new(
+                PRINTER_FIRMWARE_NAME,
+                IppValue::ListOf(vec![
+                    IppValue::NameWithoutLanguage("main".to_owned()),
+                    IppValue::NameWithoutLanguage("engine".to_owned()),
+                ]),
+            ),
+            IppAttribute::new(
+                PRINTER_FIRMWARE_STRING_VERSION,
+                IppValue::ListOf(vec![
+                    IppValue::TextWithoutLanguage("1.2.3".to_owned()),
+                    IppValue::TextWithoutLanguage("4.5".to_owned()),
+                ]),
+            ),
+        ];
+        for attr in attrs {
+            group.attributes_mut().insert(attr.name().to_owned(), attr);
+        }
+
+        let identity = PrinterInfo::from_group(&group).device_identity();
+        assert_eq!(identity.make_and_model, Some("ACME LaserJet 9000".to_owned()));
+        assert_eq!(identity.device_id, None);
+        assert_eq!(identity.firmware_names, vec!["main", "engine"]);
+        assert_eq!(identity.firmware_versions, vec!["1.2.3", "4.5"]);
+    }
+
+    #[test]
+    fn test_validate_job_attributes() {
+        let mut group = IppAttributeGroup::new(DelimiterTag::PrinterAttributes);
+        let attrs = vec![
+            IppAttribute::new(
+                SIDES_SUPPORTED,
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("one-sided".to_owned()),
+                    IppValue::Keyword("two-sided-long-edge".to_owned()),
+                ]),
+            ),
+            IppAttribute::new(COPIES_SUPPORTED, IppValue::RangeOfInteger { min: 1, max: 99 }),
+            IppAttribute::new("page-ranges-supported", IppValue::Boolean(false)),
+        ];
+        for attr in attrs {
+            group.attributes_mut().insert(attr.name().to_owned(), attr);
+        }
+        let mut attributes = IppAttributes::new();
+        attributes.groups_mut().push(group);
+
+        let caps = PrinterCapabilities::from_attributes(&attributes);
+
+        assert!(caps
+            .validate_job_attributes(&[
+                IppAttribute::new(SIDES, IppValue::Keyword("two-sided-long-edge".to_owned())),
+                IppAttribute::new("copies", IppValue::Integer(5)),
+                IppAttribute::new("unknown", IppValue::Integer(1)),
+            ])
+            .is_ok());
+
+        assert_eq!(
+            caps.validate_job_attributes(&[
+                IppAttribute::new(SIDES, IppValue::Keyword("two-sided-short-edge".to_owned())),
+                IppAttribute::new("copies", IppValue::Integer(100)),
+                IppAttribute::new("page-ranges", IppValue::RangeOfInteger { min: 1, max: 2 }),
+            ]),
+            Err(vec![SIDES.to_owned(), "copies".to_owned(), "page-ranges".to_owned()])
+        );
+    }
+
+    #[test]
+    fn test_media_col_ready() {
+        let media = |source: &str| {
+            IppValue::collection(vec![
+                (
+                    MEDIA_SIZE,
+                    IppValue::collection(vec![
+                        (X_DIMENSION, IppValue::Integer(21000)),
+                        (Y_DIMENSION, IppValue::Integer(29700)),
+                    ]),
+                ),
+                (MEDIA_SOURCE, IppValue::Keyword(source.to_owned())),
+            ])
+        };
+
+        let mut attributes = IppAttributes::new();
+        attributes.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                MEDIA_COL_READY,
+                IppValue::ListOf(vec![media("tray-1"), media("tray-2")]),
+            ),
+        );
+
+        let ready = PrinterCapabilities::from_attributes(&attributes).media_col_ready();
+        assert_eq!(ready.len(), 2);
+        assert_eq!(ready[1].source, Some("tray-2".to_owned()));
+        assert_eq!(ready[0].size.map(|s| s.x_dimension), Some(21000));
+    }
+
+    #[test]
+    fn test_is_compatible_with() {
+        let caps = |media: &[&str], sides: &[&str]| {
+            let mut attributes = IppAttributes::new();
+            let keywords =
+                |values: &[&str]| IppValue::ListOf(values.iter().map(|v| IppValue::Keyword(v.to_string())).collect());
+            attributes.add(
+                DelimiterTag::PrinterAttributes,
+                IppAttribute::new(MEDIA_SUPPORTED, keywords(media)),
+            );
+            attributes.add(
+                DelimiterTag::PrinterAttributes,
+                IppAttribute::new(SIDES_SUPPORTED, keywords(sides)),
+            );
+            attributes.add(
+                DelimiterTag::PrinterAttributes,
+                IppAttribute::new(
+                    DOCUMENT_FORMAT_SUPPORTED,
+                    IppValue::MimeMediaType("application/pdf".to_owned()),
+                ),
+            );
+            PrinterCapabilities::from_attributes(&attributes)
+        };
+
+        let small = caps(&["iso_a4_210x297mm"], &["one-sided"]);
+        let large = caps(
+            &["iso_a4_210x297mm", "na_letter_8.5x11in"],
+            &["one-sided", "two-sided-long-edge"],
+        );
+        let other = caps(&["na_letter_8.5x11in"], &["one-sided", "two-sided-long-edge"]);
+
+        assert!(small.is_compatible_with(&large));
+        assert!(small.is_compatible_with(&small));
+        assert!(!large.is_compatible_with(&small));
+        assert!(!small.is_compatible_with(&other));
+        assert!(!small.is_compatible_with(&PrinterCapabilities::from_attributes(&IppAttributes::new())));
+        assert!(PrinterCapabilities::from_attributes(&IppAttributes::new()).is_compatible_with(&small));
+    }
+
+    #[test]
+    fn test_supports_color() {
+        let caps = |attrs: Vec<IppAttribute>| {
+            let mut attributes = IppAttributes::new();
+            for attr in attrs {
+                attributes.add(DelimiterTag::PrinterAttributes, attr);
+            }
+            PrinterCapabilities::from_attributes(&attributes)
+        };
+
+        assert!(caps(vec![IppAttribute::new(COLOR_SUPPORTED, IppValue::Boolean(true))]).supports_color());
+        assert!(!caps(vec![IppAttribute::new(COLOR_SUPPORTED, IppValue::Boolean(false))]).supports_color());
+        assert!(caps(vec![IppAttribute::new(
+            PRINT_COLOR_MODE_SUPPORTED,
+            IppValue::ListOf(vec![
+                IppValue::Keyword("monochrome".to_owned()),
+                IppValue::Keyword("color".to_owned()),
+            ]),
+        )])
+        .supports_color());
+        assert!(!caps(vec![IppAttribute::new(
+            PRINT_COLOR_MODE_SUPPORTED,
+            IppValue::Keyword("monochrome".to_owned()),
+        )])
+        .supports_color());
+        assert!(!caps(Vec::new()).supports_color());
+    }
+
+    #[test]
+    fn test_pages_per_minute() {
+        let mut attributes = IppAttributes::new();
+        attributes.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PAGES_PER_MINUTE, IppValue::Integer(40)),
+        );
+        let caps = PrinterCapabilities::from_attributes(&attributes);
+        assert_eq!(caps.pages_per_minute(), Some(40));
+        assert_eq!(caps.pages_per_minute_color(), None);
+
+        attributes.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PAGES_PER_MINUTE_COLOR, IppValue::Integer(30)),
+        );
+        let caps = PrinterCapabilities::from_attributes(&attributes);
+        assert_eq!(caps.pages_per_minute_color(), Some(30));
+    }
+
+    #[test]
+    fn test_mandatory_job_attributes() {
+        let mut attributes = IppAttributes::new();
+        let caps = PrinterCapabilities::from_attributes(&attributes);
+        assert!(caps.mandatory_job_attributes().is_empty());
+
+        attributes.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_MANDATORY_JOB_ATTRIBUTES,
+                IppValue::ListOf(vec![
+                    IppValue::Keyword(MEDIA.to_owned()),
+                    IppValue::Keyword(SIDES.to_owned()),
+                ]),
+            ),
+        );
+        let caps = PrinterCapabilities::from_attributes(&attributes);
+        assert_eq!(caps.mandatory_job_attributes(), vec![MEDIA, SIDES]);
+    }
+
+    #[test]
+    fn test_printer_kind() {
+        let mut attributes = IppAttributes::new();
+        attributes.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_KIND, IppValue::Keyword("label".to_owned())),
+        );
+        let caps = PrinterCapabilities::from_attributes(&attributes);
+        assert_eq!(caps.printer_kind(), vec![PrinterKind::Label]);
+
+        let mut attributes = IppAttributes::new();
+        attributes.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_KIND,
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("document".to_owned()),
+                    IppValue::Keyword("envelope".to_owned()),
+                    IppValue::Keyword("x-sticker".to_owned()),
+                ]),
+            ),
+        );
+        let caps = PrinterCapabilities::from_attributes(&attributes);
+        assert_eq!(
+            caps.printer_kind(),
+            vec![
+                PrinterKind::Document,
+                PrinterKind::Envelope,
+                PrinterKind::Other("x-sticker".to_owned())
+            ]
+        );
+        assert_eq!(PrinterKind::Other("x-sticker".to_owned()).to_keyword(), "x-sticker");
+
+        assert!(PrinterCapabilities::from_attributes(&IppAttributes::new())
+            .printer_kind()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_trays() {
+        let mut attributes = IppAttributes::new();
+        attributes.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_INPUT_TRAY,
+                IppValue::ListOf(vec![
+                    IppValue::OctetString(
+                        "type=other;mediafeed=0;mediaxfeed=0;maxcapacity=-2;level=-2;status=0;name=auto;".to_owned(),
+                    ),
+                    IppValue::OctetString(
+                        "type=sheetFeedAutoRemovableTray;mediafeed=0;mediaxfeed=0;maxcapacity=250;level=50;status=0;name=Tray 2;"
+                            .to_owned(),
+                    ),
+                ]),
+            ),
+        );
+        attributes.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_OUTPUT_TRAY,
+                IppValue::OctetString(
+                    "type=unRemovableBin;maxcapacity=150;remaining=-3;status=0;name=Face Down;stackingorder=lastToFirst;pagedelivery=faceDown;".to_owned(),
+                ),
+            ),
+        );
+        let caps = PrinterCapabilities::from_attributes(&attributes);
+
+        let input = caps.input_trays();
+        assert_eq!(input.len(), 2);
+        assert_eq!(input[1].name, Some("Tray 2".to_owned()));
+        assert_eq!(input[1].level, Some(50));
+
+        let output = caps.output_trays();
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].tray_type, Some("unRemovableBin".to_owned()));
+        assert_eq!(output[0].level, Some(-3));
+
+        assert!(PrinterCapabilities::from_attributes(&IppAttributes::new())
+            .input_trays()
+            .is_empty());
+    }
+}
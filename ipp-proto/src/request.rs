@@ -1,22 +1,71 @@
 //!
 //! IPP request
 //!
-use std::io::{self, Cursor, Write};
+use std::io::{self, Cursor, Read, Write};
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use enum_as_inner::EnumAsInner;
-use futures::Stream;
+use futures::{
+    future::{self, Loop},
+    stream, Future, Stream,
+};
 use log::debug;
 use tempfile::NamedTempFile;
 
 use crate::{
     attribute::*,
     ipp::{DelimiterTag, IppVersion, Operation},
-    parser::IppParseResult,
+    parser::{IppParseResult, IppParser, ParseError},
     value::*,
-    IppHeader, IppJobSource, IppWriter, StatusCode,
+    IppHeader, IppJobSource, IppWriter, StatusCode, TransferMode,
 };
 
+// printer-uri operation attribute should use ipp/ipps scheme, the rest of the uri is preserved
+fn to_printer_uri(uri: &str) -> String {
+    if uri.starts_with("http://") || uri.starts_with("https://") {
+        format!("ipp{}", &uri[4..])
+    } else {
+        uri.to_owned()
+    }
+}
+
+// Number of leading document bytes which are needed for the format detection
+const FORMAT_MAGIC_LEN: usize = 4;
+
+/// Detect document-format from the first bytes of the document: `application/pdf`, `image/jpeg`
+/// and `application/postscript` are recognized, everything else is `application/octet-stream`
+pub fn detect_document_format(data: &[u8]) -> &'static str {
+    if data.starts_with(b"%PDF") {
+        "application/pdf"
+    } else if data.starts_with(&[0xff, 0xd8, 0xff]) {
+        "image/jpeg"
+    } else if data.starts_with(b"%!PS") {
+        "application/postscript"
+    } else {
+        "application/octet-stream"
+    }
+}
+
+// Read the first bytes of the document for the format detection, return them with the rest of the source
+fn peek_document(source: IppJobSource) -> impl Future<Item = (Bytes, IppJobSource), Error = io::Error> {
+    future::loop_fn((BytesMut::new(), source), |(mut data, source)| {
+        source
+            .into_future()
+            .map_err(|(e, _)| e)
+            .map(|(chunk, source)| match chunk {
+                Some(chunk) => {
+                    data.extend_from_slice(&chunk);
+                    if data.len() >= FORMAT_MAGIC_LEN {
+                        Loop::Break((data.freeze(), source))
+                    } else {
+                        Loop::Continue((data, source))
+                    }
+                }
+                None => Loop::Break((data.freeze(), source)),
+            })
+    })
+}
+
 /// Payload type inside the IppRequestResponse
 #[derive(EnumAsInner)]
 pub enum PayloadKind {
@@ -34,6 +83,10 @@ pub struct IppRequestResponse {
     attributes: IppAttributes,
     /// Optional payload after IPP-encoded stream (for example binary data for Print-Job operation)
     payload: Option<PayloadKind>,
+    /// Optional writer which receives a copy of the serialized request stream
+    tee: Option<Box<dyn Write + Send>>,
+    /// Whether document-format is detected from the payload when the request is streamed
+    detect_format: bool,
 }
 
 impl IppRequestResponse {
@@ -44,6 +97,8 @@ impl IppRequestResponse {
             header: hdr,
             attributes: IppAttributes::new(),
             payload: None,
+            tee: None,
+            detect_format: false,
         };
 
         retval.attributes_mut().add(
@@ -58,7 +113,7 @@ impl IppRequestResponse {
         if let Some(uri) = uri {
             retval.attributes_mut().add(
                 DelimiterTag::OperationAttributes,
-                IppAttribute::new(PRINTER_URI, IppValue::Uri(uri.replace("http", "ipp").to_string())),
+                IppAttribute::new(PRINTER_URI, IppValue::Uri(to_printer_uri(uri))),
             );
         }
 
@@ -72,6 +127,8 @@ impl IppRequestResponse {
             header: hdr,
             attributes: IppAttributes::new(),
             payload: None,
+            tee: None,
+            detect_format: false,
         };
 
         retval.attributes_mut().add(
@@ -92,9 +149,34 @@ impl IppRequestResponse {
             header: result.header,
             attributes: result.attributes,
             payload: result.payload,
+            tee: None,
+            detect_format: false,
         }
     }
 
+    /// Parse encoded IPP request or response without a network call, for example from a captured
+    /// `.ipp` dump. Data after the end of attributes is returned as `PayloadKind::ReceivedData` payload
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<IppRequestResponse, ParseError> {
+        let mut result = IppParser::new(&mut reader).parse()?;
+
+        let mut buf = [0u8; 4096];
+        let size = reader.read(&mut buf)?;
+        if size > 0 {
+            let mut temp = NamedTempFile::new()?;
+            debug!("Writing trailing payload to {}", temp.path().display());
+            temp.write_all(&buf[..size])?;
+            io::copy(&mut reader, &mut temp)?;
+            result.payload = Some(PayloadKind::ReceivedData(temp));
+        }
+
+        Ok(IppRequestResponse::from_parse_result(result))
+    }
+
+    /// Parse encoded IPP request or response from a byte slice
+    pub fn from_bytes(data: &[u8]) -> Result<IppRequestResponse, ParseError> {
+        IppRequestResponse::from_reader(Cursor::new(data))
+    }
+
     /// Get IPP header
     pub fn header(&self) -> &IppHeader {
         &self.header
@@ -130,6 +212,27 @@ impl IppRequestResponse {
         self.payload = Some(PayloadKind::JobSource(payload))
     }
 
+    /// Write a copy of the request stream produced by `into_stream` to the given writer,
+    /// for example for archival. Data is written chunk by chunk as it is streamed
+    pub fn tee_to(&mut self, writer: Box<dyn Write + Send>) {
+        self.tee = Some(writer);
+    }
+
+    /// Enable or disable detection of document-format operation attribute from the first bytes
+    /// of the payload, see `detect_document_format`. Detection happens in `into_stream` and is
+    /// skipped if the request already has document-format attribute
+    pub fn set_detect_format(&mut self, detect: bool) {
+        self.detect_format = detect;
+    }
+
+    /// Get HTTP transfer mode of the request. Requests without payload are always buffered
+    pub fn transfer_mode(&self) -> TransferMode {
+        match self.payload {
+            Some(PayloadKind::JobSource(ref source)) => source.transfer_mode(),
+            _ => TransferMode::Buffered,
+        }
+    }
+
     /// Serialize request into the binary stream (TCP)
     pub fn write(&mut self, writer: &mut dyn Write) -> io::Result<usize> {
         let mut retval = self.header.write(writer)?;
@@ -141,19 +244,195 @@ impl IppRequestResponse {
         Ok(retval)
     }
 
+    /// Serialize the whole request, including the document payload, into a single buffer,
+    /// for example to inspect the exact bytes without sending them. The payload is read to the end
+    pub fn into_bytes(self) -> impl Future<Item = Bytes, Error = io::Error> {
+        self.into_stream().concat2()
+    }
+
+    // Encode header and attributes into a single-chunk stream
+    fn encode_headers(
+        header: &IppHeader,
+        attributes: &IppAttributes,
+    ) -> impl Stream<Item = Bytes, Error = io::Error> + Send + 'static {
+        let mut cursor = Cursor::new(Vec::with_capacity(1024));
+        let _ = header.write(&mut cursor).and_then(|_| attributes.write(&mut cursor));
+
+        stream::once(Ok(cursor.into_inner().into()))
+    }
+
     /// Convert request/response into Stream
     pub fn into_stream(self) -> Box<dyn Stream<Item = Bytes, Error = io::Error> + Send + 'static> {
-        let mut cursor = Cursor::new(Vec::with_capacity(1024));
-        let _ = self
-            .header
-            .write(&mut cursor)
-            .and_then(|_| self.attributes.write(&mut cursor));
+        let has_format = self
+            .attributes
+            .groups_of(DelimiterTag::OperationAttributes)
+            .iter()
+            .any(|g| g.attributes().contains_key(DOCUMENT_FORMAT));
 
-        let headers = futures::stream::once(Ok(cursor.into_inner().into()));
+        let stream: Box<dyn Stream<Item = Bytes, Error = io::Error> + Send + 'static> = match self.payload {
+            Some(PayloadKind::JobSource(payload)) if self.detect_format && !has_format => {
+                let header = self.header;
+                let mut attributes = self.attributes;
+                Box::new(
+                    peek_document(payload)
+                        .map(move |(data, payload)| {
+                            let format = detect_document_format(&data);
+                            debug!("Detected document format: {}", format);
+                            attributes.add(
+                                DelimiterTag::OperationAttributes,
+                                IppAttribute::new(DOCUMENT_FORMAT, IppValue::MimeMediaType(format.to_owned())),
+                            );
+                            let data = if data.is_empty() { None } else { Some(data) };
+                            IppRequestResponse::encode_headers(&header, &attributes)
+                                .chain(stream::iter_ok(data))
+                                .chain(payload)
+                        })
+                        .flatten_stream(),
+                )
+            }
+            Some(PayloadKind::JobSource(payload)) => {
+                Box::new(IppRequestResponse::encode_headers(&self.header, &self.attributes).chain(payload))
+            }
+            _ => Box::new(IppRequestResponse::encode_headers(&self.header, &self.attributes)),
+        };
 
-        match self.payload {
-            Some(PayloadKind::JobSource(payload)) => Box::new(headers.chain(payload)),
-            _ => Box::new(headers),
+        match self.tee {
+            Some(mut tee) => Box::new(stream.and_then(move |chunk| tee.write_all(&chunk).map(|_| chunk))),
+            None => stream,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn printer_uri(req: &IppRequestResponse) -> Option<&IppValue> {
+        req.attributes().groups_of(DelimiterTag::OperationAttributes)[0]
+            .attributes()
+            .get(PRINTER_URI)
+            .map(|a| a.value())
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        // Get-Printer-Attributes response dump with two printer-state-reasons values
+        let data = include_bytes!("../testdata/get-printer-attributes.ipp");
+
+        let response = IppRequestResponse::from_bytes(data).unwrap();
+        assert_eq!(response.header().version, IppVersion::Ipp11);
+        assert_eq!(response.header().status_code(), Some(StatusCode::SuccessfulOK));
+        assert_eq!(response.header().request_id, 42);
+        assert!(response.payload().is_none());
+
+        let printer = response.attributes().groups_of(DelimiterTag::PrinterAttributes)[0].attributes();
+        assert_eq!(
+            printer[PRINTER_STATE].value(),
+            &IppValue::Enum(crate::ipp::PrinterState::Processing as i32)
+        );
+        assert_eq!(
+            printer[PRINTER_STATE_REASONS].value(),
+            &IppValue::ListOf(vec![
+                IppValue::Keyword("media-low-warning".to_owned()),
+                IppValue::Keyword("toner-low-report".to_owned()),
+            ])
+        );
+
+        let attrs = IppAttributes::from_reader(&data[..]).unwrap();
+        assert_eq!(&attrs, response.attributes());
+
+        assert!(IppAttributes::from_bytes(&data[..20]).is_err());
+    }
+
+    #[test]
+    fn test_from_bytes_trailing_data() {
+        let mut data = Vec::new();
+        IppRequestResponse::new(IppVersion::Ipp11, Operation::PrintJob, Some("ipp://localhost"))
+            .write(&mut data)
+            .unwrap();
+        data.extend_from_slice(b"%PDF-1.4");
+
+        let request = IppRequestResponse::from_bytes(&data).unwrap();
+        assert_eq!(request.header().operation().unwrap(), Operation::PrintJob);
+        match request.payload() {
+            Some(PayloadKind::ReceivedData(f)) => assert_eq!(std::fs::read(f.path()).unwrap(), b"%PDF-1.4"),
+            _ => panic!("Expected received data payload"),
         }
     }
+
+    #[test]
+    fn test_detect_document_format() {
+        assert_eq!(detect_document_format(b"%PDF-1.7\n"), "application/pdf");
+        assert_eq!(detect_document_format(&[0xff, 0xd8, 0xff, 0xe0, 0, 0x10]), "image/jpeg");
+        assert_eq!(detect_document_format(b"%!PS-Adobe-3.0\n"), "application/postscript");
+        assert_eq!(detect_document_format(b"plain text"), "application/octet-stream");
+        assert_eq!(detect_document_format(b"%PD"), "application/octet-stream");
+        assert_eq!(detect_document_format(b""), "application/octet-stream");
+    }
+
+    #[test]
+    fn test_into_stream_detect_format() {
+        let chunks = vec![Bytes::from_static(b"%P"), Bytes::from_static(b"DF-1.4 document")];
+        let mut req = IppRequestResponse::new(IppVersion::Ipp11, Operation::PrintJob, Some("ipp://host/ipp/print"));
+        req.add_payload(IppJobSource::from_stream(stream::iter_ok(chunks)));
+        req.set_detect_format(true);
+
+        let data = req.into_bytes().wait().unwrap();
+        assert!(data.ends_with(b"%PDF-1.4 document"));
+
+        let req = IppRequestResponse::from_bytes(&data).unwrap();
+        let attrs = req.attributes().groups_of(DelimiterTag::OperationAttributes)[0].attributes();
+        assert_eq!(
+            attrs[DOCUMENT_FORMAT].value(),
+            &IppValue::MimeMediaType("application/pdf".to_owned())
+        );
+
+        let mut req = IppRequestResponse::new(IppVersion::Ipp11, Operation::PrintJob, Some("ipp://host/ipp/print"));
+        req.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(DOCUMENT_FORMAT, IppValue::MimeMediaType("image/pwg-raster".to_owned())),
+        );
+        req.add_payload(IppJobSource::from_bytes(&b"%PDF-1.4"[..]));
+        req.set_detect_format(true);
+
+        let req = IppRequestResponse::from_bytes(&req.into_bytes().wait().unwrap()).unwrap();
+        let attrs = req.attributes().groups_of(DelimiterTag::OperationAttributes)[0].attributes();
+        assert_eq!(
+            attrs[DOCUMENT_FORMAT].value(),
+            &IppValue::MimeMediaType("image/pwg-raster".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_printer_uri_deep_path() {
+        let req = IppRequestResponse::new(
+            IppVersion::Ipp11,
+            Operation::GetPrinterAttributes,
+            Some("https://host/ipp/print/floor2"),
+        );
+        assert_eq!(
+            printer_uri(&req),
+            Some(&IppValue::Uri("ipps://host/ipp/print/floor2".to_owned()))
+        );
+
+        let req = IppRequestResponse::new(
+            IppVersion::Ipp11,
+            Operation::GetPrinterAttributes,
+            Some("http://host:8080/printers/http-printer"),
+        );
+        assert_eq!(
+            printer_uri(&req),
+            Some(&IppValue::Uri("ipp://host:8080/printers/http-printer".to_owned()))
+        );
+
+        let req = IppRequestResponse::new(
+            IppVersion::Ipp11,
+            Operation::GetPrinterAttributes,
+            Some("ipp://host/ipp/print"),
+        );
+        assert_eq!(
+            printer_uri(&req),
+            Some(&IppValue::Uri("ipp://host/ipp/print".to_owned()))
+        );
+    }
 }
@@ -0,0 +1,116 @@
+//!
+//! Printer tray helpers
+//!
+use std::{collections::HashMap, iter};
+
+/// Input or output tray, parsed from printer-input-tray or printer-output-tray attribute.
+/// The attribute values use PWG 5100.13 key/value syntax, for example
+/// `type=sheetFeedAutoRemovableTray;maxcapacity=250;level=100;status=0;name=Tray 1;`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Tray {
+    /// type key, for example `sheetFeedAutoRemovableTray` or `unRemovableBin`
+    pub tray_type: Option<String>,
+    /// name key, for example `Tray 1`
+    pub name: Option<String>,
+    /// maxcapacity key: capacity in sheets. Negative values are special: -2 is unknown, -3 is at least one
+    pub max_capacity: Option<i32>,
+    /// level key of input trays or remaining key of output trays, with the same special values as max_capacity
+    pub level: Option<i32>,
+    /// status key: bit field of the tray state, 0 means available and idle
+    pub status: Option<i32>,
+    /// All keys and values of the tray, including the ones not listed above
+    pub properties: HashMap<String, String>,
+}
+
+// Split tray string into key/value pairs. Values may contain backslash-escaped semicolons
+fn parse_properties(value: &str) -> HashMap<String, String> {
+    let mut properties = HashMap::new();
+    let mut item = String::new();
+    let mut escaped = false;
+
+    for c in value.chars().chain(iter::once(';')) {
+        match c {
+            _ if escaped => {
+                item.push(c);
+                escaped = false;
+            }
+            '\\' => escaped = true,
+            ';' => {
+                let mut parts = item.splitn(2, '=');
+                if let (Some(key), Some(value)) = (parts.next(), parts.next()) {
+                    if !key.trim().is_empty() {
+                        properties.insert(key.trim().to_ascii_lowercase(), value.to_owned());
+                    }
+                }
+                item.clear();
+            }
+            c => item.push(c),
+        }
+    }
+
+    properties
+}
+
+impl Tray {
+    /// Parse tray from printer-input-tray or printer-output-tray value.
+    /// Unknown keys are kept in `properties`, malformed numbers are ignored
+    pub fn parse(value: &str) -> Tray {
+        let properties = parse_properties(value);
+        let number = |key: &str| properties.get(key).and_then(|v| v.trim().parse::<i32>().ok());
+
+        let max_capacity = number("maxcapacity");
+        let level = number("level").or_else(|| number("remaining"));
+        let status = number("status");
+
+        Tray {
+            tray_type: properties.get("type").cloned(),
+            name: properties.get("name").cloned(),
+            max_capacity,
+            level,
+            status,
+            properties,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_tray() {
+        let tray = Tray::parse(
+            "type=sheetFeedAutoRemovableTray;mediafeed=0;mediaxfeed=0;maxcapacity=250;level=-3;status=0;name=Tray 1;",
+        );
+        assert_eq!(tray.tray_type, Some("sheetFeedAutoRemovableTray".to_owned()));
+        assert_eq!(tray.name, Some("Tray 1".to_owned()));
+        assert_eq!(tray.max_capacity, Some(250));
+        assert_eq!(tray.level, Some(-3));
+        assert_eq!(tray.status, Some(0));
+        assert_eq!(tray.properties.get("mediafeed"), Some(&"0".to_owned()));
+    }
+
+    #[test]
+    fn test_output_tray() {
+        let tray = Tray::parse(
+            "type=unRemovableBin;maxcapacity=150;remaining=-3;status=5;name=Face\\;Down;stackingorder=lastToFirst",
+        );
+        assert_eq!(tray.tray_type, Some("unRemovableBin".to_owned()));
+        assert_eq!(tray.name, Some("Face;Down".to_owned()));
+        assert_eq!(tray.level, Some(-3));
+        assert_eq!(tray.status, Some(5));
+        assert_eq!(tray.properties.get("stackingorder"), Some(&"lastToFirst".to_owned()));
+    }
+
+    #[test]
+    fn test_malformed_tray() {
+        let tray = Tray::parse("type=;maxcapacity=lots;;garbage;level=");
+        assert_eq!(tray.tray_type, Some("".to_owned()));
+        assert_eq!(tray.max_capacity, None);
+        assert_eq!(tray.level, None);
+        assert_eq!(tray.name, None);
+        assert!(!tray.properties.contains_key("garbage"));
+
+        assert_eq!(Tray::parse(""), Tray::default());
+    }
+}
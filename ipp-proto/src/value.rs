@@ -4,6 +4,7 @@
 use std::{
     fmt,
     io::{self, Read, Write},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
@@ -13,14 +14,58 @@ use num_traits::FromPrimitive;
 
 use crate::{ipp::ValueTag, IppReadExt, IppWriter};
 
+// number of days since 1970-01-01 for a given date in proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let yoe = year - era * 400;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+// date (year, month, day) for a given number of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = if days >= 0 { days } else { days - 146_096 } / 146_097;
+    let doe = days - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+// Split textWithLanguage or nameWithLanguage value into language and text,
+// each of them is prefixed with a two-byte length
+fn read_with_language(data: &[u8]) -> io::Result<(String, String)> {
+    let mut reader = io::Cursor::new(data);
+    let lang_len = reader.read_u16::<BigEndian>()?;
+    let lang = reader.read_string(lang_len as usize)?;
+    let text_len = reader.read_u16::<BigEndian>()?;
+    let text = reader.read_string(text_len as usize)?;
+    Ok((lang, text))
+}
+
 /// IPP value enumeration
 #[derive(Clone, Debug, PartialEq, EnumAsInner)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IppValue {
     Integer(i32),
     Enum(i32),
     OctetString(String),
     TextWithoutLanguage(String),
     NameWithoutLanguage(String),
+    TextWithLanguage {
+        lang: String,
+        text: String,
+    },
+    NameWithLanguage {
+        lang: String,
+        name: String,
+    },
     Charset(String),
     NaturalLanguage(String),
     Uri(String),
@@ -51,12 +96,32 @@ pub enum IppValue {
         feed: i32,
         units: i8,
     },
+    /// Out-of-band value to delete the attribute in Set-*-Attributes operations
+    DeleteAttribute,
+    /// Out-of-band value to use the administratively defined value in Set-*-Attributes operations
+    AdminDefine,
     Other {
         tag: u8,
+        #[cfg_attr(feature = "serde", serde(with = "bytes_serde"))]
         data: Bytes,
     },
 }
 
+// Serialization of raw out-of-band data as a byte array
+#[cfg(feature = "serde")]
+mod bytes_serde {
+    use bytes::Bytes;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(data: &Bytes, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(data)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Bytes, D::Error> {
+        Vec::<u8>::deserialize(deserializer).map(Bytes::from)
+    }
+}
+
 impl IppValue {
     /// Convert to binary tag
     pub fn to_tag(&self) -> ValueTag {
@@ -69,6 +134,8 @@ impl IppValue {
             IppValue::OctetString(_) => ValueTag::OctetStringUnspecified,
             IppValue::TextWithoutLanguage(_) => ValueTag::TextWithoutLanguage,
             IppValue::NameWithoutLanguage(_) => ValueTag::NameWithoutLanguage,
+            IppValue::TextWithLanguage { .. } => ValueTag::TextWithLanguage,
+            IppValue::NameWithLanguage { .. } => ValueTag::NameWithLanguage,
             IppValue::Charset(_) => ValueTag::Charset,
             IppValue::NaturalLanguage(_) => ValueTag::NaturalLanguage,
             IppValue::Uri(_) => ValueTag::Uri,
@@ -78,10 +145,191 @@ impl IppValue {
             IppValue::DateTime { .. } => ValueTag::DateTime,
             IppValue::MemberAttrName(_) => ValueTag::MemberAttrName,
             IppValue::Resolution { .. } => ValueTag::Resolution,
+            IppValue::DeleteAttribute => ValueTag::DeleteAttribute,
+            IppValue::AdminDefine => ValueTag::AdminDefine,
             IppValue::Other { .. } => ValueTag::Unknown,
         }
     }
 
+    /// Return string slice for string-based values (keyword, text, name, uri, etc)
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            IppValue::Keyword(ref s)
+            | IppValue::OctetString(ref s)
+            | IppValue::TextWithoutLanguage(ref s)
+            | IppValue::NameWithoutLanguage(ref s)
+            | IppValue::Charset(ref s)
+            | IppValue::NaturalLanguage(ref s)
+            | IppValue::Uri(ref s)
+            | IppValue::MimeMediaType(ref s)
+            | IppValue::MemberAttrName(ref s)
+            | IppValue::TextWithLanguage { text: ref s, .. }
+            | IppValue::NameWithLanguage { name: ref s, .. } => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Return numeric value as i64. IPP integer values are signed 32-bit, so counters which may
+    /// grow larger are sometimes reported by printers as decimal octetString. Integer values are
+    /// widened and numeric octetString values are parsed, other values return None.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            IppValue::Integer(i) => Some(i64::from(i)),
+            IppValue::OctetString(ref s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Create collection value from a list of member names and values
+    pub fn collection<I, S>(members: I) -> IppValue
+    where
+        I: IntoIterator<Item = (S, IppValue)>,
+        S: AsRef<str>,
+    {
+        let mut list = Vec::new();
+        for (name, value) in members {
+            list.push(IppValue::MemberAttrName(name.as_ref().to_owned()));
+            match value {
+                IppValue::ListOf(values) => list.extend(values),
+                value => list.push(value),
+            }
+        }
+        IppValue::Collection(list)
+    }
+
+    /// Return collection members as a list of (name, value) pairs. Multi-valued members are returned as ListOf.
+    /// For non-collection values an empty list is returned.
+    pub fn members(&self) -> Vec<(&str, IppValue)> {
+        let mut members: Vec<(&str, Vec<IppValue>)> = Vec::new();
+
+        if let IppValue::Collection(ref list) = *self {
+            for item in list {
+                match item {
+                    IppValue::MemberAttrName(ref name) => members.push((name, Vec::new())),
+                    value => {
+                        if let Some(member) = members.last_mut() {
+                            member.1.push(value.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        members
+            .into_iter()
+            .map(|(name, mut values)| {
+                if values.len() == 1 {
+                    (name, values.remove(0))
+                } else {
+                    (name, IppValue::ListOf(values))
+                }
+            })
+            .collect()
+    }
+
+    /// Find collection member value by name
+    pub fn member(&self, name: &str) -> Option<IppValue> {
+        self.members().into_iter().find(|m| m.0 == name).map(|m| m.1)
+    }
+
+    /// Format value in IPP text syntax as used by `lpoptions` and `-o` command-line options:
+    /// lists are comma-separated, ranges use a dash and collections are enclosed in braces
+    pub fn to_ipp_syntax(&self) -> String {
+        match *self {
+            IppValue::RangeOfInteger { min, max } if min == max => min.to_string(),
+            IppValue::RangeOfInteger { min, max } => format!("{}-{}", min, max),
+            IppValue::Resolution { crossfeed, feed, units } => {
+                format!("{}x{}{}", crossfeed, feed, if units == 3 { "dpi" } else { "dpcm" })
+            }
+            IppValue::ListOf(ref list) => list.iter().map(|v| v.to_ipp_syntax()).collect::<Vec<_>>().join(","),
+            IppValue::Collection(_) => {
+                let members = self
+                    .members()
+                    .into_iter()
+                    .map(|(name, value)| format!("{}={}", name, value.to_ipp_syntax()))
+                    .collect::<Vec<_>>();
+                format!("{{{}}}", members.join(" "))
+            }
+            _ => match self.as_str() {
+                Some(s) if s.is_empty() || s.contains(|c: char| c.is_whitespace() || c == ',' || c == '\'') => {
+                    format!("\"{}\"", s)
+                }
+                _ => self.to_string(),
+            },
+        }
+    }
+
+    /// Convert DateTime value to SystemTime, taking into account UTC offset.
+    /// Returns None for other value types.
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        match *self {
+            IppValue::DateTime {
+                year,
+                month,
+                day,
+                hour,
+                minutes,
+                seconds,
+                deciseconds,
+                utcdir,
+                utchours,
+                utcmins,
+            } => {
+                let offset = i64::from(utchours) * 3600 + i64::from(utcmins) * 60;
+                let offset = if utcdir == '-' { -offset } else { offset };
+                let secs = days_from_civil(i64::from(year), i64::from(month), i64::from(day)) * 86400
+                    + i64::from(hour) * 3600
+                    + i64::from(minutes) * 60
+                    + i64::from(seconds)
+                    - offset;
+                let millis = Duration::from_millis(u64::from(deciseconds) * 100);
+
+                if secs >= 0 {
+                    Some(UNIX_EPOCH + Duration::from_secs(secs as u64) + millis)
+                } else {
+                    Some(UNIX_EPOCH - Duration::from_secs(-secs as u64) + millis)
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Convert DateTime value to chrono date and time with the UTC offset of the value.
+    /// Returns None for other value types or invalid dates.
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        match *self {
+            IppValue::DateTime {
+                year,
+                month,
+                day,
+                hour,
+                minutes,
+                seconds,
+                deciseconds,
+                utcdir,
+                utchours,
+                utcmins,
+            } => {
+                use chrono::TimeZone;
+
+                let offset = i32::from(utchours) * 3600 + i32::from(utcmins) * 60;
+                let offset = if utcdir == '-' { -offset } else { offset };
+                let local = chrono::NaiveDate::from_ymd_opt(i32::from(year), u32::from(month), u32::from(day))?
+                    .and_hms_milli_opt(
+                        u32::from(hour),
+                        u32::from(minutes),
+                        u32::from(seconds),
+                        u32::from(deciseconds) * 100,
+                    )?;
+                chrono::FixedOffset::east_opt(offset)?
+                    .from_local_datetime(&local)
+                    .single()
+            }
+            _ => None,
+        }
+    }
+
     /// Read value from binary stream
     pub fn read(vtag: u8, reader: &mut dyn Read) -> io::Result<IppValue> {
         let vsize = reader.read_u16::<BigEndian>()?;
@@ -111,6 +359,14 @@ impl IppValue {
             ValueTag::Charset => Ok(IppValue::Charset(reader.read_string(vsize as usize)?)),
             ValueTag::NaturalLanguage => Ok(IppValue::NaturalLanguage(reader.read_string(vsize as usize)?)),
             ValueTag::Uri => Ok(IppValue::Uri(reader.read_string(vsize as usize)?)),
+            ValueTag::TextWithLanguage => {
+                let (lang, text) = read_with_language(&reader.read_bytes(vsize as usize)?)?;
+                Ok(IppValue::TextWithLanguage { lang, text })
+            }
+            ValueTag::NameWithLanguage => {
+                let (lang, name) = read_with_language(&reader.read_bytes(vsize as usize)?)?;
+                Ok(IppValue::NameWithLanguage { lang, name })
+            }
             ValueTag::RangeOfInteger => {
                 debug_assert_eq!(vsize, 8);
                 Ok(IppValue::RangeOfInteger {
@@ -142,6 +398,14 @@ impl IppValue {
                 feed: reader.read_i32::<BigEndian>()?,
                 units: reader.read_i8()?,
             }),
+            ValueTag::DeleteAttribute => {
+                reader.read_bytes(vsize as usize)?;
+                Ok(IppValue::DeleteAttribute)
+            }
+            ValueTag::AdminDefine => {
+                reader.read_bytes(vsize as usize)?;
+                Ok(IppValue::AdminDefine)
+            }
             _ => Ok(IppValue::Other {
                 tag: vtag,
                 data: reader.read_bytes(vsize as usize)?,
@@ -183,6 +447,15 @@ impl IppWriter for IppValue {
                 writer.write_all(s.as_bytes())?;
                 Ok(2 + s.len())
             }
+            IppValue::TextWithLanguage { ref lang, text: ref s }
+            | IppValue::NameWithLanguage { ref lang, name: ref s } => {
+                writer.write_u16::<BigEndian>((4 + lang.len() + s.len()) as u16)?;
+                writer.write_u16::<BigEndian>(lang.len() as u16)?;
+                writer.write_all(lang.as_bytes())?;
+                writer.write_u16::<BigEndian>(s.len() as u16)?;
+                writer.write_all(s.as_bytes())?;
+                Ok(6 + lang.len() + s.len())
+            }
             IppValue::ListOf(ref list) => {
                 let mut retval = 0;
                 for (i, item) in list.iter().enumerate() {
@@ -251,6 +524,10 @@ impl IppWriter for IppValue {
                 writer.write_i8(units)?;
                 Ok(9)
             }
+            IppValue::DeleteAttribute | IppValue::AdminDefine => {
+                writer.write_u16::<BigEndian>(0)?;
+                Ok(2)
+            }
             IppValue::Other { ref data, .. } => {
                 writer.write_u16::<BigEndian>(data.len() as u16)?;
                 writer.write_all(data)?;
@@ -260,12 +537,70 @@ impl IppWriter for IppValue {
     }
 }
 
+impl From<SystemTime> for IppValue {
+    /// Create DateTime value in UTC from SystemTime
+    fn from(time: SystemTime) -> Self {
+        let (secs, millis) = match time.duration_since(UNIX_EPOCH) {
+            Ok(d) => (d.as_secs() as i64, d.subsec_millis()),
+            Err(e) => {
+                let d = e.duration();
+                let secs = -(d.as_secs() as i64);
+                if d.subsec_millis() > 0 {
+                    (secs - 1, 1000 - d.subsec_millis())
+                } else {
+                    (secs, 0)
+                }
+            }
+        };
+        let days = secs.div_euclid(86400);
+        let rem = secs.rem_euclid(86400);
+        let (year, month, day) = civil_from_days(days);
+
+        IppValue::DateTime {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+            hour: (rem / 3600) as u8,
+            minutes: (rem % 3600 / 60) as u8,
+            seconds: (rem % 60) as u8,
+            deciseconds: (millis / 100) as u8,
+            utcdir: '+',
+            utchours: 0,
+            utcmins: 0,
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for IppValue {
+    /// Create DateTime value from chrono date and time, keeping its UTC offset
+    fn from(time: chrono::DateTime<Tz>) -> Self {
+        use chrono::{Datelike, Offset, Timelike};
+
+        let offset = time.offset().fix().local_minus_utc();
+        let local = time.naive_local();
+
+        IppValue::DateTime {
+            year: local.year() as u16,
+            month: local.month() as u8,
+            day: local.day() as u8,
+            hour: local.hour() as u8,
+            minutes: local.minute() as u8,
+            seconds: local.second() as u8,
+            deciseconds: (local.nanosecond() / 100_000_000) as u8,
+            utcdir: if offset < 0 { '-' } else { '+' },
+            utchours: (offset.abs() / 3600) as u8,
+            utcmins: (offset.abs() % 3600 / 60) as u8,
+        }
+    }
+}
+
 /// Implement Display trait to print the value
 impl fmt::Display for IppValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             IppValue::Integer(i) | IppValue::Enum(i) => write!(f, "{}", i),
-            IppValue::RangeOfInteger { min, max } => write!(f, "{}..{}", min, max),
+            IppValue::RangeOfInteger { min, max } => write!(f, "{}-{}", min, max),
             IppValue::Boolean(b) => write!(f, "{}", if b { "true" } else { "false" }),
             IppValue::Keyword(ref s)
             | IppValue::OctetString(ref s)
@@ -276,13 +611,28 @@ impl fmt::Display for IppValue {
             | IppValue::Uri(ref s)
             | IppValue::MimeMediaType(ref s)
             | IppValue::MemberAttrName(ref s) => write!(f, "{}", s),
+            IppValue::TextWithLanguage { ref lang, text: ref s }
+            | IppValue::NameWithLanguage { ref lang, name: ref s } => write!(f, "{} ({})", s, lang),
             IppValue::ListOf(ref list) => {
                 let s: Vec<String> = list.iter().map(|v| format!("{}", v)).collect();
                 write!(f, "[{}]", s.join(", "))
             }
             IppValue::Collection(ref list) => {
-                let s: Vec<String> = list.iter().map(|v| format!("{}", v)).collect();
-                write!(f, "<{}>", s.join(", "))
+                // member name followed by its values, multiple values are comma-separated
+                let mut members: Vec<String> = Vec::new();
+                for item in list {
+                    match (item, members.last_mut()) {
+                        (IppValue::MemberAttrName(ref name), _) => members.push(format!("{}=", name)),
+                        (value, Some(member)) if member.ends_with('=') => member.push_str(&value.to_string()),
+                        (value, Some(member)) => member.push_str(&format!(",{}", value)),
+                        (value, None) => members.push(value.to_string()),
+                    }
+                }
+                if members.is_empty() {
+                    write!(f, "{{}}")
+                } else {
+                    write!(f, "{{ {} }}", members.join(", "))
+                }
             }
             IppValue::DateTime {
                 year,
@@ -294,16 +644,18 @@ impl fmt::Display for IppValue {
                 deciseconds,
                 utcdir,
                 utchours,
-                ..
+                utcmins,
             } => write!(
                 f,
-                "{}-{}-{},{}:{}:{}.{},{}{}utc",
-                year, month, day, hour, minutes, seconds, deciseconds, utcdir as char, utchours
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{}{}{:02}:{:02}",
+                year, month, day, hour, minutes, seconds, deciseconds, utcdir, utchours, utcmins
             ),
             IppValue::Resolution { crossfeed, feed, units } => {
-                write!(f, "{}x{}{}", crossfeed, feed, if units == 3 { "in" } else { "cm" })
+                write!(f, "{}x{}{}", crossfeed, feed, if units == 3 { "dpi" } else { "dpcm" })
             }
 
+            IppValue::DeleteAttribute => write!(f, "delete-attribute"),
+            IppValue::AdminDefine => write!(f, "admin-define"),
             IppValue::Other { tag, ref data } => write!(f, "{:0x}: {:?}", tag, data),
         }
     }
@@ -354,6 +706,191 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_date_time_system_time() {
+        let value = IppValue::DateTime {
+            year: 2019,
+            month: 7,
+            day: 14,
+            hour: 12,
+            minutes: 30,
+            seconds: 15,
+            deciseconds: 5,
+            utcdir: '+',
+            utchours: 2,
+            utcmins: 0,
+        };
+        let time = value.to_system_time().unwrap();
+        assert_eq!(
+            time.duration_since(UNIX_EPOCH).unwrap(),
+            Duration::from_millis(1_563_100_215_500)
+        );
+
+        assert_eq!(
+            IppValue::from(time),
+            IppValue::DateTime {
+                year: 2019,
+                month: 7,
+                day: 14,
+                hour: 10,
+                minutes: 30,
+                seconds: 15,
+                deciseconds: 5,
+                utcdir: '+',
+                utchours: 0,
+                utcmins: 0,
+            }
+        );
+        assert_eq!(IppValue::from(UNIX_EPOCH).to_system_time(), Some(UNIX_EPOCH));
+        assert_eq!(IppValue::Integer(1).to_system_time(), None);
+    }
+
+    #[test]
+    fn test_date_time_display() {
+        let value = IppValue::DateTime {
+            year: 2019,
+            month: 7,
+            day: 4,
+            hour: 8,
+            minutes: 5,
+            seconds: 9,
+            deciseconds: 5,
+            utcdir: '-',
+            utchours: 5,
+            utcmins: 30,
+        };
+        assert_eq!(value.to_string(), "2019-07-04T08:05:09.5-05:30");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_date_time_chrono() {
+        use chrono::TimeZone;
+
+        let value = IppValue::DateTime {
+            year: 2019,
+            month: 12,
+            day: 31,
+            hour: 22,
+            minutes: 15,
+            seconds: 30,
+            deciseconds: 7,
+            utcdir: '-',
+            utchours: 3,
+            utcmins: 30,
+        };
+
+        let mut buf = Vec::new();
+        value.write(&mut buf).unwrap();
+        assert_eq!(buf, vec![0, 11, 0x07, 0xe3, 12, 31, 22, 15, 30, 7, b'-', 3, 30]);
+        let read = IppValue::read(ValueTag::DateTime as u8, &mut io::Cursor::new(&buf)).unwrap();
+        assert_eq!(read, value);
+
+        let time = value.to_chrono().unwrap();
+        assert_eq!(time.offset().local_minus_utc(), -(3 * 3600 + 30 * 60));
+        assert_eq!(
+            time.with_timezone(&chrono::Utc),
+            chrono::Utc.with_ymd_and_hms(2020, 1, 1, 1, 45, 30).unwrap() + chrono::Duration::milliseconds(700)
+        );
+        assert_eq!(IppValue::from(time), value);
+        assert_eq!(
+            IppValue::from(time.with_timezone(&chrono::Utc)).to_system_time(),
+            value.to_system_time()
+        );
+        assert_eq!(IppValue::Integer(1).to_chrono(), None);
+    }
+
+    #[test]
+    fn test_with_language_round_trip() {
+        let text = IppValue::TextWithLanguage {
+            lang: "fr-CA".to_owned(),
+            text: "Imprimante du rez-de-chaussée".to_owned(),
+        };
+        let name = IppValue::NameWithLanguage {
+            lang: "zh-Hans".to_owned(),
+            name: "打印机".to_owned(),
+        };
+
+        for value in &[text.clone(), name.clone()] {
+            let mut buf = Vec::new();
+            let size = value.write(&mut buf).unwrap();
+            assert_eq!(size, buf.len());
+            let read = IppValue::read(value.to_tag() as u8, &mut io::Cursor::new(&buf)).unwrap();
+            assert_eq!(&read, value);
+
+            let mut written = Vec::new();
+            read.write(&mut written).unwrap();
+            assert_eq!(written, buf);
+        }
+
+        assert_eq!(text.to_string(), "Imprimante du rez-de-chaussée (fr-CA)");
+        assert_eq!(name.to_string(), "打印机 (zh-Hans)");
+        assert_eq!(name.as_str(), Some("打印机"));
+
+        // language length exceeds the value size
+        let data = [0, 4, 0, 9, b'e', b'n'];
+        assert!(IppValue::read(ValueTag::TextWithLanguage as u8, &mut io::Cursor::new(&data[..])).is_err());
+    }
+
+    #[test]
+    fn test_range_of_integer_round_trip() {
+        // reversed range is not validated and must be preserved as is
+        for &(min, max) in &[(1, 5), (7, 3)] {
+            let value = IppValue::RangeOfInteger { min, max };
+            let mut buf = Vec::new();
+            assert_eq!(value.write(&mut buf).unwrap(), 10);
+            assert_eq!(&buf[2..6], &min.to_be_bytes());
+            assert_eq!(&buf[6..], &max.to_be_bytes());
+            assert_eq!(
+                IppValue::read(ValueTag::RangeOfInteger as u8, &mut io::Cursor::new(&buf)).unwrap(),
+                value
+            );
+            assert_eq!(value.to_string(), format!("{}-{}", min, max));
+        }
+    }
+
+    #[test]
+    fn test_resolution_round_trip() {
+        for (value, text) in &[
+            (
+                IppValue::Resolution {
+                    crossfeed: 600,
+                    feed: 600,
+                    units: 3,
+                },
+                "600x600dpi",
+            ),
+            (
+                IppValue::Resolution {
+                    crossfeed: 118,
+                    feed: 236,
+                    units: 4,
+                },
+                "118x236dpcm",
+            ),
+        ] {
+            let mut buf = Vec::new();
+            assert_eq!(value.write(&mut buf).unwrap(), 9);
+            assert_eq!(buf.len(), 11);
+            assert_eq!(
+                IppValue::read(ValueTag::Resolution as u8, &mut io::Cursor::new(&buf)).unwrap(),
+                *value
+            );
+            assert_eq!(value.to_string(), *text);
+        }
+    }
+
+    #[test]
+    fn test_as_i64() {
+        assert_eq!(IppValue::Integer(-5).as_i64(), Some(-5));
+        assert_eq!(
+            IppValue::OctetString("8589934592".to_owned()).as_i64(),
+            Some(8_589_934_592)
+        );
+        assert_eq!(IppValue::OctetString("n/a".to_owned()).as_i64(), None);
+        assert_eq!(IppValue::Keyword("42".to_owned()).as_i64(), None);
+    }
+
     #[test]
     fn test_value_iterator_single() {
         let val = IppValue::Integer(1234);
@@ -405,4 +942,74 @@ mod tests {
             Some(&vec![IppValue::Integer(0x11111111), IppValue::Integer(0x22222222)])
         );
     }
+
+    #[test]
+    fn test_nested_collection_round_trip() {
+        let media_size = IppValue::collection(vec![
+            ("x-dimension", IppValue::Integer(21000)),
+            ("y-dimension", IppValue::Integer(29700)),
+        ]);
+        let media_col = IppValue::collection(vec![
+            ("media-size", media_size.clone()),
+            ("media-top-margin", IppValue::Integer(300)),
+        ]);
+        let attr = IppAttribute::new("media-col", media_col.clone());
+
+        let mut buf = Vec::new();
+        attr.write(&mut io::Cursor::new(&mut buf)).unwrap();
+
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 2];
+        data.extend(&buf);
+        data.push(3);
+
+        let result = crate::parser::IppParser::new(&mut io::Cursor::new(data))
+            .parse()
+            .unwrap();
+        let parsed = result.attributes.groups_of(DelimiterTag::JobAttributes)[0].attributes()["media-col"].clone();
+        assert_eq!(parsed.value(), &media_col);
+        assert_eq!(parsed.value().member("media-size"), Some(media_size));
+
+        let mut written = Vec::new();
+        parsed.write(&mut io::Cursor::new(&mut written)).unwrap();
+        assert_eq!(written, buf);
+
+        assert_eq!(
+            media_col.to_string(),
+            "{ media-size={ x-dimension=21000, y-dimension=29700 }, media-top-margin=300 }"
+        );
+        assert_eq!(
+            IppValue::collection(vec![(
+                "media-source",
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("main".to_owned()),
+                    IppValue::Keyword("manual".to_owned()),
+                ])
+            )])
+            .to_string(),
+            "{ media-source=main,manual }"
+        );
+    }
+
+    #[test]
+    fn test_out_of_band_de_serialize() {
+        let attr = IppAttribute::new("job-hold-until", IppValue::DeleteAttribute);
+        let mut buf = Vec::new();
+        assert!(attr.write(&mut io::Cursor::new(&mut buf)).is_ok());
+
+        let mut expected = vec![0x16, 0, 14];
+        expected.extend(b"job-hold-until");
+        expected.extend(&[0, 0]);
+        assert_eq!(buf, expected);
+
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4];
+        data.extend(buf);
+        data.extend(&[0x17, 0, 1, b'x', 0, 0, 3]);
+
+        let res = crate::parser::IppParser::new(&mut io::Cursor::new(data))
+            .parse()
+            .unwrap();
+        let attrs = res.attributes.groups_of(DelimiterTag::PrinterAttributes)[0].attributes();
+        assert_eq!(attrs.get("job-hold-until").unwrap().value(), &IppValue::DeleteAttribute);
+        assert_eq!(attrs.get("x").unwrap().value(), &IppValue::AdminDefine);
+    }
 }
@@ -2,15 +2,24 @@
 //! High-level utility functions to be used from external application or command-line utility
 //!
 
-use std::{ffi::OsString, io, path::PathBuf};
+use std::{
+    ffi::OsString,
+    io::{self, Write},
+    path::PathBuf,
+    time::Duration,
+};
 
 use futures::{future, Future};
 use structopt::StructOpt;
-use tokio::io::AsyncRead;
+use tokio::{io::AsyncRead, util::FutureExt};
 
 use ipp_client::{IppClient, IppClientBuilder, IppError};
-use ipp_proto::ipp::DelimiterTag;
-use ipp_proto::{IppAttribute, IppOperationBuilder, IppValue};
+use ipp_proto::ipp::{DelimiterTag, StatusCode};
+use ipp_proto::operation::{CancelJob, GetJobs, HoldNewJobs, PurgeJobs, ReleaseHeldNewJobs};
+use ipp_proto::{attribute::JOB_ID, IppAttribute, IppOperationBuilder, IppValue, JobInfo};
+
+// Timeout for the soft printer state check
+const SOFT_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
 
 fn new_client(uri: &str, params: &IppParams) -> IppClient {
     IppClientBuilder::new(&uri)
@@ -41,7 +50,20 @@ fn do_print(params: &IppParams, cmd: IppPrintCmd) -> Result<(), IppError> {
 
     let client = new_client(&cmd.uri, params);
 
-    if !cmd.no_check_state {
+    if cmd.dry_run {
+        // the printer is not contacted
+    } else if cmd.check_state_soft {
+        // only the printer state errors are fatal, everything else means "assume ready"
+        match runtime.block_on(client.check_ready().timeout(SOFT_CHECK_TIMEOUT)) {
+            Ok(()) => {}
+            Err(ref e) if e.is_elapsed() => eprintln!("WARNING: printer state check timed out, assuming ready"),
+            Err(e) => match e.into_inner() {
+                Some(e @ IppError::PrinterStopped) | Some(e @ IppError::PrinterStateError(_)) => return Err(e),
+                Some(e) => eprintln!("WARNING: printer state check failed, assuming ready: {}", e),
+                None => eprintln!("WARNING: printer state check failed, assuming ready"),
+            },
+        }
+    } else if !cmd.no_check_state {
         runtime.block_on(client.check_ready())?;
     }
 
@@ -70,14 +92,22 @@ fn do_print(params: &IppParams, cmd: IppPrintCmd) -> Result<(), IppError> {
             }
         }
 
-        client.send(builder.build()).and_then(|attrs| {
+        if cmd.dry_run {
+            return future::Either::A(
+                client
+                    .encode_request(builder.build())
+                    .and_then(|data| io::stdout().write_all(&data).map_err(IppError::from)),
+            );
+        }
+
+        future::Either::B(client.send(builder.build()).and_then(|attrs| {
             if let Some(group) = attrs.groups_of(DelimiterTag::JobAttributes).get(0) {
                 for v in group.attributes().values() {
                     println!("{}: {}", v.name(), v.value());
                 }
             }
             Ok(())
-        })
+        }))
     }))
 }
 
@@ -91,7 +121,7 @@ fn do_status(params: &IppParams, cmd: IppStatusCmd) -> Result<(), IppError> {
     let mut runtime = tokio::runtime::Runtime::new().unwrap();
     let attrs = runtime.block_on(client.send(operation))?;
 
-    if let Some(group) = attrs.groups_of(DelimiterTag::PrinterAttributes).get(0) {
+    if let Some(group) = attrs.groups_of(cmd.group).first() {
         let mut values: Vec<_> = group.attributes().values().collect();
         values.sort_by(|a, b| a.name().cmp(b.name()));
         for v in values {
@@ -101,6 +131,80 @@ fn do_status(params: &IppParams, cmd: IppStatusCmd) -> Result<(), IppError> {
     Ok(())
 }
 
+fn is_not_supported(error: &IppError) -> bool {
+    matches!(
+        error,
+        IppError::OperationNotSupported(_) | IppError::Status(StatusCode::ServerErrorOperationNotSupported)
+    )
+}
+
+fn do_purge(params: &IppParams, cmd: IppPurgeCmd) -> Result<(), IppError> {
+    if !cmd.yes {
+        return Err(IppError::ParamError(
+            "Purge cancels all jobs on the printer, use --yes to confirm".to_owned(),
+        ));
+    }
+
+    let client = new_client(&cmd.uri, params);
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let mut held = false;
+    if cmd.hold_new {
+        match runtime.block_on(client.send(HoldNewJobs::new())) {
+            Ok(_) => held = true,
+            Err(ref e) if is_not_supported(e) => eprintln!("WARNING: printer does not support holding new jobs"),
+            Err(e) => return Err(e),
+        }
+    }
+
+    let result = purge_jobs(&mut runtime, &client);
+
+    // release the hold even if the purge failed, otherwise the printer keeps holding new jobs
+    if held {
+        if let Err(e) = runtime.block_on(client.send(ReleaseHeldNewJobs::new())) {
+            eprintln!("WARNING: failed to release held new jobs: {}", e);
+        }
+    }
+    result
+}
+
+fn purge_jobs(runtime: &mut tokio::runtime::Runtime, client: &IppClient) -> Result<(), IppError> {
+    // the job list is only used for the report and the Cancel-Job fallback,
+    // so a failing Get-Jobs must not prevent the purge
+    let jobs = runtime
+        .block_on(client.send(GetJobs::with_attributes(Some("not-completed"), &[JOB_ID])))
+        .map(|attrs| {
+            attrs
+                .groups_of(DelimiterTag::JobAttributes)
+                .into_iter()
+                .filter_map(|group| JobInfo::from_group(group).id())
+                .collect::<Vec<i32>>()
+        });
+
+    match runtime.block_on(client.send(PurgeJobs::new())) {
+        Ok(_) => match jobs {
+            Ok(jobs) => println!("Purged {} job(s)", jobs.len()),
+            Err(e) => {
+                eprintln!("WARNING: failed to list jobs: {}", e);
+                println!("Purged all jobs");
+            }
+        },
+        Err(ref e) if is_not_supported(e) => {
+            // fall back to canceling the pending jobs one by one
+            let mut canceled = 0;
+            for id in jobs? {
+                match runtime.block_on(client.send(CancelJob::new(id))) {
+                    Ok(_) => canceled += 1,
+                    Err(e) => eprintln!("WARNING: failed to cancel job {}: {}", id, e),
+                }
+            }
+            println!("Canceled {} job(s)", canceled);
+        }
+        Err(e) => return Err(e),
+    }
+    Ok(())
+}
+
 #[derive(StructOpt)]
 #[structopt(name = "IPP print utility", about = "", author = "", rename_all = "kebab-case")]
 struct IppParams {
@@ -145,6 +249,8 @@ enum IppCommand {
     Print(IppPrintCmd),
     #[structopt(name = "status", about = "Get status of an IPP printer", author = "")]
     Status(IppStatusCmd),
+    #[structopt(name = "purge", about = "Cancel all jobs on an IPP printer", author = "")]
+    Purge(IppPurgeCmd),
 }
 
 #[derive(StructOpt, Clone)]
@@ -160,6 +266,13 @@ struct IppPrintCmd {
     )]
     no_check_state: bool,
 
+    #[structopt(
+        long = "check-state-soft",
+        conflicts_with = "no-check-state",
+        help = "Check printer state with a short timeout, proceed with a warning on timeout or error"
+    )]
+    check_state_soft: bool,
+
     #[structopt(
         long = "file",
         short = "f",
@@ -179,6 +292,12 @@ struct IppPrintCmd {
 
     #[structopt(long = "option", short = "o", help = "Extra IPP job attributes in key=value format")]
     options: Vec<String>,
+
+    #[structopt(
+        long = "dry-run",
+        help = "Write the encoded IPP request to standard output instead of sending it"
+    )]
+    dry_run: bool,
 }
 
 #[derive(StructOpt, Clone)]
@@ -189,6 +308,30 @@ struct IppStatusCmd {
 
     #[structopt(long = "attribute", short = "a", help = "Attributes to query, default is to get all")]
     attributes: Vec<String>,
+
+    #[structopt(
+        default_value = "printer-attributes",
+        long = "group",
+        short = "g",
+        help = "Attribute group to display"
+    )]
+    group: DelimiterTag,
+}
+
+#[derive(StructOpt, Clone)]
+#[structopt(name = "IPP print utility", about = "", author = "", rename_all = "kebab-case")]
+struct IppPurgeCmd {
+    #[structopt(help = "Printer URI")]
+    uri: String,
+
+    #[structopt(
+        long = "hold-new",
+        help = "Hold new jobs submitted while purging and release them afterwards"
+    )]
+    hold_new: bool,
+
+    #[structopt(long = "yes", help = "Confirm canceling all jobs")]
+    yes: bool,
 }
 
 /// Entry point to main utility function
@@ -209,6 +352,7 @@ struct IppStatusCmd {
 /// OPTIONS:
 ///     -a, --attribute <attributes>...   Attributes to query, default is to get all
 ///     -c, --ca-cert <ca-certs>...       Additional CA root certificates in PEM or DER format
+///     -g, --group <group>               Attribute group to display [default: printer-attributes]
 ///     -t, --timeout <timeout>           Network timeout in seconds, 0 to disable [default: 30]
 ///
 /// ARGS:
@@ -222,6 +366,8 @@ struct IppStatusCmd {
 ///
 /// FLAGS:
 ///     -h, --help                     Prints help information
+///     --check-state-soft             Check printer state with a short timeout, proceed with a warning on timeout or error
+///     --dry-run                      Write the encoded IPP request to standard output instead of sending it
 ///     -n, --no-check-state           Do not check printer state before printing
 ///     --no-verify-certificate        Disable TLS certificate verification (insecure)
 ///     --no-verify-hostname           Disable TLS host name verification (insecure)
@@ -238,6 +384,27 @@ struct IppStatusCmd {
 /// ARGS:
 ///     <uri>    Printer URI
 /// ```
+///
+/// Command line usage for canceling all jobs (will fall back to Cancel-Job if Purge-Jobs is not supported)
+/// ```text
+/// USAGE:
+///     ipputil purge [FLAGS] [OPTIONS] <uri>
+///
+/// FLAGS:
+///     -h, --help                     Prints help information
+///     --hold-new                     Hold new jobs submitted while purging
+///     --no-verify-certificate        Disable TLS certificate verification (insecure)
+///     --no-verify-hostname           Disable TLS host name verification (insecure)
+///     -V, --version                  Prints version information
+///     --yes                          Confirm canceling all jobs
+///
+/// OPTIONS:
+///     -c, --ca-cert <ca-certs>...    Additional CA root certificates in PEM or DER format
+///     -t, --timeout <timeout>        Network timeout in seconds, 0 to disable [default: 30]
+///
+/// ARGS:
+///     <uri>    Printer URI
+/// ```
 pub fn ipp_main<I, T>(args: I) -> Result<(), IppError>
 where
     I: IntoIterator<Item = T>,
@@ -247,6 +414,7 @@ where
     match params.command {
         IppCommand::Status(ref cmd) => do_status(&params, cmd.clone())?,
         IppCommand::Print(ref cmd) => do_print(&params, cmd.clone())?,
+        IppCommand::Purge(ref cmd) => do_purge(&params, cmd.clone())?,
     }
     Ok(())
 }
@@ -15,13 +15,12 @@ pub fn main() -> Result<(), Box<dyn Error>> {
         exit(1);
     }
 
-    let mut runtime = tokio::runtime::Runtime::new()?;
-    let client = IppClientBuilder::new(&args[1]).build();
+    let mut client = IppClientBuilder::new(&args[1]).build_blocking()?;
     let operation = IppOperationBuilder::get_printer_attributes()
         .attributes(&args[2..])
         .build();
 
-    let attrs = runtime.block_on(client.send(operation))?;
+    let attrs = client.send(operation)?;
 
     for v in attrs.groups_of(DelimiterTag::PrinterAttributes)[0]
         .attributes()
@@ -1,13 +1,8 @@
 use std::{env, error::Error, process::exit};
 
-use num_traits::cast::FromPrimitive;
-
 use ipp::{
-    client::{IppClientBuilder, IppError},
-    proto::{
-        ipp::{DelimiterTag, PrinterState},
-        operation::cups::CupsGetPrinters,
-    },
+    client::IppClientBuilder,
+    proto::{IppOperationBuilder, PrinterInfo},
 };
 
 pub fn main() -> Result<(), Box<dyn Error>> {
@@ -16,26 +11,27 @@ pub fn main() -> Result<(), Box<dyn Error>> {
     let args: Vec<_> = env::args().collect();
 
     if args.len() < 2 {
-        println!("Usage: {} uri", args[0]);
+        println!("Usage: {} uri [first-printer-name limit]", args[0]);
         exit(1);
     }
 
     let mut runtime = tokio::runtime::Runtime::new()?;
     let client = IppClientBuilder::new(&args[1]).build();
-    let operation = CupsGetPrinters::new();
 
-    let attrs = runtime.block_on(client.send(operation))?;
+    let mut builder = IppOperationBuilder::cups_get_printers();
+    if args.len() > 3 {
+        builder = builder.first_printer_name(&args[2]).limit(args[3].parse()?);
+    }
 
-    for group in attrs.groups_of(DelimiterTag::PrinterAttributes) {
-        let name = group.attributes()["printer-name"].value();
-        let uri = group.attributes()["device-uri"].value();
-        let state = group.attributes()["printer-state"]
-            .value()
-            .as_enum()
-            .and_then(|v| PrinterState::from_i32(*v))
-            .ok_or(IppError::InvalidAttributeType)?;
+    let attrs = runtime.block_on(client.send(builder.build()))?;
 
-        println!("{}: {} {:?}", name, uri, state);
+    for printer in PrinterInfo::list(&attrs) {
+        println!(
+            "{}: {} {:?}",
+            printer.name().unwrap_or_default(),
+            printer.device_uri().unwrap_or_default(),
+            printer.state()
+        );
     }
 
     Ok(())